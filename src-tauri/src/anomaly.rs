@@ -0,0 +1,165 @@
+use std::cmp::Ordering;
+use crate::rng::Rng;
+
+
+// average path length of an unsuccessful search in a binary search tree of n points
+fn average_path(n: usize) -> f64 {
+    match n {
+        0 | 1 => 0.0,
+        2 => 1.0,
+        _ => {
+            let n = n as f64;
+            2.0 * ((n - 1.0).ln() + 0.577_215_664_9) - 2.0 * (n - 1.0) / n
+        }
+    }
+}
+
+enum IsolationNode {
+    Leaf { size: usize },
+    Split { feature: usize, value: f64, left: Box<IsolationNode>, right: Box<IsolationNode> }
+}
+
+fn isolation_tree(points: &[Vec<f64>], rows: Vec<usize>, depth: usize, max_depth: usize, rng: &mut Rng) -> IsolationNode {
+    if rows.len() <= 1 || depth >= max_depth {
+        return IsolationNode::Leaf { size: rows.len() };
+    }
+
+    // only features that still vary can separate the points
+    let dims = points[rows[0]].len();
+    let candidates: Vec<(usize, f64, f64)> = (0..dims).filter_map(|f| {
+        let (min, max) = rows.iter().map(|r| points[*r][f]).fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        if max > min { Some((f, min, max)) } else { None }
+    }).collect();
+    if candidates.is_empty() {
+        return IsolationNode::Leaf { size: rows.len() };
+    }
+
+    let (feature, min, max) = candidates[rng.below(candidates.len())];
+    let value = min + rng.next_f64() * (max - min);
+    let (left, right): (Vec<usize>, Vec<usize>) = rows.iter().partition(|r| points[**r][feature] < value);
+    IsolationNode::Split {
+        feature,
+        value,
+        left: Box::new(isolation_tree(points, left, depth + 1, max_depth, rng)),
+        right: Box::new(isolation_tree(points, right, depth + 1, max_depth, rng))
+    }
+}
+
+fn path_length(node: &IsolationNode, point: &[f64], depth: usize) -> f64 {
+    match node {
+        IsolationNode::Leaf { size } => depth as f64 + average_path(*size),
+        IsolationNode::Split { feature, value, left, right } => {
+            if point[*feature] < *value {
+                path_length(left, point, depth + 1)
+            } else {
+                path_length(right, point, depth + 1)
+            }
+        }
+    }
+}
+
+// Isolation forest anomaly scores in (0, 1], scores close to 1 are anomalies
+pub fn isolation_forest(points: &[Vec<f64>], trees: usize, sample_size: usize, seed: u64) -> Vec<f64> {
+    if points.is_empty() {
+        return vec![];
+    }
+    let mut rng = Rng::new(seed);
+    let sample_size = sample_size.clamp(2, points.len().max(2)).min(points.len());
+    let max_depth = (sample_size as f64).log2().ceil() as usize;
+
+    let forest: Vec<IsolationNode> = (0..trees.max(1)).map(|_| {
+        let mut rows: Vec<usize> = (0..points.len()).collect();
+        rng.shuffle(&mut rows);
+        rows.truncate(sample_size);
+        isolation_tree(points, rows, 0, max_depth, &mut rng)
+    }).collect();
+
+    let normaliser = average_path(sample_size).max(f64::EPSILON);
+    points.iter().map(|p| {
+        let mean_path = forest.iter().map(|tree| path_length(tree, p, 0)).sum::<f64>() / forest.len() as f64;
+        2f64.powf(-mean_path / normaliser)
+    }).collect()
+}
+
+fn present(column: &[f64]) -> Vec<f64> {
+    let mut values: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    values
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+// largest absolute z-score of a row over all columns, missing values are ignored
+pub fn z_scores(columns: &[Vec<f64>]) -> Vec<Option<f64>> {
+    let n = columns.first().map_or(0, |c| c.len());
+    let stats: Vec<Option<(f64, f64)>> = columns.iter().map(|column| {
+        let values = present(column);
+        if values.is_empty() {
+            return None;
+        }
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt();
+        if std > 0.0 { Some((mean, std)) } else { None }
+    }).collect();
+
+    (0..n).map(|r| {
+        columns.iter().zip(&stats)
+               .filter_map(|(column, stat)| stat.and_then(|(mean, std)| if column[r].is_nan() { None } else { Some(((column[r] - mean) / std).abs()) }))
+               .fold(None, |max: Option<f64>, z| Some(max.map_or(z, |m| m.max(z))))
+    }).collect()
+}
+
+// largest distance outside of the quartiles of a row over all columns, in multiples of the
+// interquartile range. Values inside the box score 0.
+pub fn iqr_scores(columns: &[Vec<f64>]) -> Vec<Option<f64>> {
+    let n = columns.first().map_or(0, |c| c.len());
+    let stats: Vec<Option<(f64, f64)>> = columns.iter().map(|column| {
+        let values = present(column);
+        if values.is_empty() {
+            return None;
+        }
+        Some((quantile(&values, 0.25), quantile(&values, 0.75)))
+    }).collect();
+
+    (0..n).map(|r| {
+        columns.iter().zip(&stats)
+               .filter_map(|(column, stat)| stat.and_then(|(q1, q3)| {
+                   if column[r].is_nan() {
+                       return None;
+                   }
+                   let outside = (q1 - column[r]).max(column[r] - q3).max(0.0);
+                   let iqr = q3 - q1;
+                   Some(if iqr > 0.0 { outside / iqr } else if outside > 0.0 { f64::INFINITY } else { 0.0 })
+               }))
+               .fold(None, |max: Option<f64>, s| Some(max.map_or(s, |m| m.max(s))))
+    }).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isolation_forest_scores_the_outlier_highest() {
+        let mut points: Vec<Vec<f64>> = (0..50).map(|i| vec![(i % 7) as f64 * 0.1, (i % 5) as f64 * 0.1]).collect();
+        points.push(vec![25.0, -25.0]);
+        let scores = isolation_forest(&points, 100, 32, 7);
+        let outlier = scores[50];
+        assert!(scores[..50].iter().all(|s| *s < outlier));
+        assert!(outlier > 0.6);
+    }
+
+    #[test]
+    fn z_scores_flag_the_extreme_row() {
+        let column: Vec<f64> = (0..20).map(|i| if i == 19 { 100.0 } else { (i % 4) as f64 }).collect();
+        let scores = z_scores(&[column]);
+        let max = scores.iter().flatten().fold(0.0, |a: f64, b| a.max(*b));
+        assert_eq!(scores[19], Some(max));
+        assert!(max > 3.0);
+    }
+}
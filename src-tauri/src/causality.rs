@@ -0,0 +1,143 @@
+use crate::numeric::{correlation, f_sf, least_squares};
+
+
+pub struct PairResult {
+    // lag with the largest absolute cross-correlation and that correlation
+    pub correlation_lag: usize,
+    pub correlation: f64,
+    // lag with the most significant granger test
+    pub granger_lag: usize,
+    pub f_statistic: f64,
+    pub p_value: f64
+}
+
+pub fn difference(series: &[f64]) -> Vec<f64> {
+    series.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+// correlation of cause(t - lag) and effect(t), pairs with missing values are skipped
+pub fn lagged_correlation(cause: &[f64], effect: &[f64], lag: usize) -> Option<f64> {
+    let (a, b): (Vec<f64>, Vec<f64>) = (lag..effect.len().min(cause.len()))
+        .map(|t| (cause[t - lag], effect[t]))
+        .filter(|(x, y)| !x.is_nan() && !y.is_nan())
+        .unzip();
+    if a.len() < 3 { None } else { Some(correlation(&a, &b)) }
+}
+
+fn residual_sum(rows: &[Vec<f64>], y: &[f64]) -> Option<f64> {
+    let beta = least_squares(rows, y)?;
+    Some(rows.iter().zip(y).map(|(row, target)| {
+        let fitted: f64 = row.iter().zip(&beta).map(|(x, b)| x * b).sum();
+        (target - fitted).powi(2)
+    }).sum())
+}
+
+// F-test whether the lags of `cause` improve an autoregression of `effect` of the same order.
+// Returns the statistic and its p-value, None without enough complete observations.
+pub fn granger(cause: &[f64], effect: &[f64], lag: usize) -> Option<(f64, f64)> {
+    let n = effect.len().min(cause.len());
+    let mut restricted: Vec<Vec<f64>> = vec![];
+    let mut unrestricted: Vec<Vec<f64>> = vec![];
+    let mut y: Vec<f64> = vec![];
+    for t in lag..n {
+        let mut row = vec![1.0];
+        row.extend((1..=lag).map(|l| effect[t - l]));
+        let mut full = row.clone();
+        full.extend((1..=lag).map(|l| cause[t - l]));
+        if effect[t].is_nan() || full.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        restricted.push(row);
+        unrestricted.push(full);
+        y.push(effect[t]);
+    }
+
+    let dof = y.len().checked_sub(2 * lag + 1).filter(|d| *d > 0)?;
+    let (rss_r, rss_u) = (residual_sum(&restricted, &y)?, residual_sum(&unrestricted, &y)?);
+    if rss_u <= f64::EPSILON {
+        return None;
+    }
+    let statistic = ((rss_r - rss_u).max(0.0) / lag as f64) / (rss_u / dof as f64);
+    Some((statistic, f_sf(statistic, lag, dof)))
+}
+
+// Bonferroni correction of a p-value for the number of tests it was selected from, capped at 1
+pub fn bonferroni(p_value: f64, tests: usize) -> f64 {
+    (p_value * tests.max(1) as f64).min(1.0)
+}
+
+// every ordered pair of series is tested on every lag, the best p-value of a pair is corrected
+// for all of these tests
+pub fn test_count(series: usize, max_lag: usize) -> usize {
+    series * series.saturating_sub(1) * max_lag
+}
+
+// cross-correlations over the lags 0..=max_lag and granger tests over the lags 1..=max_lag
+pub fn analyse_pair(cause: &[f64], effect: &[f64], max_lag: usize) -> Option<PairResult> {
+    let (correlation_lag, correlation) = (0..=max_lag)
+        .filter_map(|lag| lagged_correlation(cause, effect, lag).map(|c| (lag, c)))
+        .fold(None, |best: Option<(usize, f64)>, curr| match best {
+            Some(b) if b.1.abs() >= curr.1.abs() => Some(b),
+            _ => Some(curr)
+        })?;
+    let (granger_lag, (f_statistic, p_value)) = (1..=max_lag.max(1))
+        .filter_map(|lag| granger(cause, effect, lag).map(|g| (lag, g)))
+        .fold(None, |best: Option<(usize, (f64, f64))>, curr| match best {
+            Some(b) if (b.1).1 <= (curr.1).1 => Some(b),
+            _ => Some(curr)
+        })?;
+    Some(PairResult { correlation_lag, correlation, granger_lag, f_statistic, p_value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // deterministic noise in [-0.5, 0.5)
+    fn noise(seed: u64, n: usize) -> Vec<f64> {
+        let mut state = seed;
+        (0..n).map(|_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        }).collect()
+    }
+
+    #[test]
+    fn bonferroni_multiplies_and_caps() {
+        assert!((bonferroni(0.01, 6) - 0.06).abs() < 1e-12);
+        assert!((bonferroni(0.004, 12) - 0.048).abs() < 1e-12);
+        assert_eq!(bonferroni(0.3, 6), 1.0);
+        assert_eq!(bonferroni(0.02, 0), 0.02);
+    }
+
+    #[test]
+    fn every_ordered_pair_and_lag_is_a_test() {
+        // 3 series give 6 ordered pairs, each tested on lags 1..=4
+        assert_eq!(test_count(3, 4), 24);
+        assert_eq!(test_count(2, 1), 2);
+        assert_eq!(test_count(1, 3), 0);
+        assert_eq!(test_count(0, 3), 0);
+    }
+
+    #[test]
+    fn granger_detects_the_driving_series() {
+        let cause = noise(1, 60);
+        let effect: Vec<f64> = noise(2, 60).iter().enumerate().map(|(t, e)| if t == 0 { *e } else { 2.0 * cause[t - 1] + 0.1 * e }).collect();
+
+        let driven = analyse_pair(&cause, &effect, 3).unwrap();
+        assert_eq!((driven.correlation_lag, driven.granger_lag), (1, 1));
+        assert!(driven.correlation > 0.9);
+        assert!(driven.p_value < 1e-10);
+        // the effect does not help to predict the cause, even after the correction
+        let reverse = analyse_pair(&effect, &cause, 3).unwrap();
+        assert!(bonferroni(reverse.p_value, test_count(2, 3)) > 0.05);
+    }
+
+    #[test]
+    fn granger_needs_enough_observations() {
+        let series = noise(3, 4);
+        assert!(granger(&series, &series, 2).is_none());
+        assert!(lagged_correlation(&series, &series, 2).is_none());
+        assert_eq!(difference(&[1.0, 4.0, 2.0]), vec![3.0, -2.0]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, VecDeque};
+use rayon::prelude::*;
+use crate::object_graph::ObjectGraph;
+
+
+// number of incident edges per node, in total and per relation type
+pub fn degrees(graph: &ObjectGraph, relations: &[String]) -> (Vec<u32>, Vec<Vec<u32>>) {
+    let mut total: Vec<u32> = vec![0; graph.node_count()];
+    let mut per_relation: Vec<Vec<u32>> = vec![vec![0; graph.node_count()]; relations.len()];
+    for edge in &graph.edges {
+        let ends: Vec<usize> = if edge.source == edge.target { vec![edge.source] } else { vec![edge.source, edge.target] };
+        for node in ends {
+            total[node] += 1;
+            relations.iter().enumerate().filter(|(_, rel)| edge.relations.contains_key(*rel)).for_each(|(r, _)| per_relation[r][node] += 1);
+        }
+    }
+    (total, per_relation)
+}
+
+// PageRank over the directed edges, the rank of nodes without outgoing edges is spread evenly
+pub fn pagerank(graph: &ObjectGraph, damping: f64, max_iterations: usize) -> Vec<f64> {
+    let n = graph.node_count();
+    if n == 0 {
+        return vec![];
+    }
+    let mut outgoing: Vec<Vec<usize>> = vec![vec![]; n];
+    graph.edges.iter().for_each(|e| outgoing[e.source].push(e.target));
+
+    let mut rank: Vec<f64> = vec![1.0 / n as f64; n];
+    for _ in 0..max_iterations {
+        let dangling: f64 = (0..n).filter(|v| outgoing[*v].is_empty()).map(|v| rank[v]).sum();
+        let mut next: Vec<f64> = vec![(1.0 - damping) / n as f64 + damping * dangling / n as f64; n];
+        for (v, targets) in outgoing.iter().enumerate().filter(|(_, t)| !t.is_empty()) {
+            let share = damping * rank[v] / targets.len() as f64;
+            targets.iter().for_each(|t| next[*t] += share);
+        }
+        let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < 1e-10 {
+            break;
+        }
+    }
+    rank
+}
+
+// Brandes' betweenness on the unweighted undirected graph, normalised to [0, 1]
+pub fn betweenness(adjacency: &[BTreeMap<usize, f64>]) -> Vec<f64> {
+    let n = adjacency.len();
+    let partial = |source: usize| -> Vec<f64> {
+        let mut stack: Vec<usize> = vec![];
+        let mut predecessors: Vec<Vec<usize>> = vec![vec![]; n];
+        let mut paths: Vec<f64> = vec![0.0; n];
+        let mut distance: Vec<i64> = vec![-1; n];
+        paths[source] = 1.0;
+        distance[source] = 0;
+        let mut queue: VecDeque<usize> = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            for w in adjacency[v].keys() {
+                if distance[*w] < 0 {
+                    distance[*w] = distance[v] + 1;
+                    queue.push_back(*w);
+                }
+                if distance[*w] == distance[v] + 1 {
+                    paths[*w] += paths[v];
+                    predecessors[*w].push(v);
+                }
+            }
+        }
+
+        let mut dependency: Vec<f64> = vec![0.0; n];
+        let mut centrality: Vec<f64> = vec![0.0; n];
+        while let Some(w) = stack.pop() {
+            for v in &predecessors[w] {
+                dependency[*v] += paths[*v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                centrality[w] += dependency[w];
+            }
+        }
+        centrality
+    };
+
+    let total: Vec<f64> = (0..n).into_par_iter().map(partial).reduce(|| vec![0.0; n], |a, b| a.iter().zip(&b).map(|(x, y)| x + y).collect());
+    // every pair is counted from both ends
+    let scale = if n > 2 { 1.0 / ((n - 1) * (n - 2)) as f64 } else { 0.0 };
+    total.into_iter().map(|c| c * scale).collect()
+}
+
+// share of the pairs of neighbours that are connected themselves
+pub fn clustering_coefficients(adjacency: &[BTreeMap<usize, f64>]) -> Vec<f64> {
+    adjacency.iter().map(|neighbours| {
+        let k = neighbours.len();
+        if k < 2 {
+            return 0.0;
+        }
+        let nodes: Vec<&usize> = neighbours.keys().collect();
+        let links = nodes.iter().enumerate().map(|(i, a)| nodes.iter().skip(i + 1).filter(|b| adjacency[**a].contains_key(**b)).count()).sum::<usize>();
+        2.0 * links as f64 / (k * (k - 1)) as f64
+    }).collect()
+}
+
+// weakly connected components, numbered by decreasing size
+pub fn components(adjacency: &[BTreeMap<usize, f64>]) -> Vec<u32> {
+    let n = adjacency.len();
+    let mut component: Vec<Option<usize>> = vec![None; n];
+    let mut sizes: Vec<usize> = vec![];
+    for start in 0..n {
+        if component[start].is_some() {
+            continue;
+        }
+        let id = sizes.len();
+        sizes.push(0);
+        component[start] = Some(id);
+        let mut queue: VecDeque<usize> = VecDeque::from([start]);
+        while let Some(v) = queue.pop_front() {
+            sizes[id] += 1;
+            for w in adjacency[v].keys() {
+                if component[*w].is_none() {
+                    component[*w] = Some(id);
+                    queue.push_back(*w);
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|c| (std::cmp::Reverse(sizes[*c]), *c));
+    let mut renumbered: Vec<u32> = vec![0; sizes.len()];
+    order.iter().enumerate().for_each(|(new, old)| renumbered[*old] = new as u32);
+    component.into_iter().map(|c| renumbered[c.expect("every node is reached from its own start")]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::object_graph::ObjectEdge;
+
+    fn graph(n: usize, edges: &[(usize, usize, &[&str])]) -> ObjectGraph {
+        ObjectGraph {
+            objects: (0..n).map(|i| format!("o{}", i)).collect(),
+            object_types: vec!["order".to_string(); n],
+            edges: edges.iter().map(|(source, target, relations)| ObjectEdge {
+                source: *source,
+                target: *target,
+                relations: relations.iter().map(|rel| (rel.to_string(), 1)).collect()
+            }).collect(),
+            status: None
+        }
+    }
+
+    fn close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        actual.iter().zip(expected).for_each(|(a, e)| assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected));
+    }
+
+    #[test]
+    fn path() {
+        let path = graph(4, &[(0, 1, &[]), (1, 2, &[]), (2, 3, &[])]).undirected(&HashMap::new());
+        // the inner nodes lie on 2 of the 3 shortest paths between the other nodes
+        close(&betweenness(&path), &[0.0, 2.0 / 3.0, 2.0 / 3.0, 0.0]);
+        close(&clustering_coefficients(&path), &[0.0; 4]);
+        assert_eq!(components(&path), vec![0; 4]);
+    }
+
+    #[test]
+    fn star() {
+        let star = graph(5, &[(0, 1, &["INTERACTS"]), (0, 2, &["INTERACTS", "COLIFE"]), (3, 0, &["COLIFE"]), (4, 0, &[])]);
+        close(&betweenness(&star.undirected(&HashMap::new())), &[1.0, 0.0, 0.0, 0.0, 0.0]);
+        close(&clustering_coefficients(&star.undirected(&HashMap::new())), &[0.0; 5]);
+        let (total, per_relation) = degrees(&star, &["INTERACTS".to_string(), "COLIFE".to_string()]);
+        assert_eq!(total, vec![4, 1, 1, 1, 1]);
+        assert_eq!(per_relation, vec![vec![2, 1, 1, 0, 0], vec![2, 0, 1, 1, 0]]);
+    }
+
+    #[test]
+    fn triangle_with_separate_pair() {
+        let adjacency = graph(6, &[(0, 1, &[]), (1, 2, &[]), (2, 0, &[]), (3, 4, &[])]).undirected(&HashMap::new());
+        close(&clustering_coefficients(&adjacency)[..3], &[1.0; 3]);
+        close(&betweenness(&adjacency)[..3], &[0.0; 3]);
+        // components by decreasing size, the isolated node comes last
+        assert_eq!(components(&adjacency), vec![0, 0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn pagerank_spreads_dangling_rank() {
+        // 0 -> 1 -> 2, the rank of 2 is spread evenly: with a = (1 - d) / 3 + d * r2 / 3 the ranks
+        // are a, a (1 + d) and a (1 + d + d^2)
+        let chain = graph(3, &[(0, 1, &[]), (1, 2, &[])]);
+        let d: f64 = 0.85;
+        let a = 1.0 / (3.0 + 2.0 * d + d * d);
+        let rank = pagerank(&chain, d, 1000);
+        close(&rank, &[a, a * (1.0 + d), a * (1.0 + d + d * d)]);
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+}
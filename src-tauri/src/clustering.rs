@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+use crate::rng::Rng;
+
+
+// label of DBSCAN noise points
+pub const NOISE: i64 = -1;
+
+// Scale every column to zero mean and unit variance, ignoring missing values. Constant columns
+// become 0.
+pub fn standardise(columns: &mut [Vec<f64>]) {
+    for column in columns.iter_mut() {
+        let present: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+        if present.is_empty() {
+            continue;
+        }
+        let mean = present.iter().sum::<f64>() / present.len() as f64;
+        let std = (present.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / present.len() as f64).sqrt();
+        column.iter_mut().filter(|v| !v.is_nan()).for_each(|v| *v = if std > 0.0 { (*v - mean) / std } else { 0.0 });
+    }
+}
+
+// row-major points of all rows without missing values, together with their row index
+pub fn complete_rows(columns: &[Vec<f64>]) -> (Vec<usize>, Vec<Vec<f64>>) {
+    let n = columns.first().map_or(0, |c| c.len());
+    (0..n).filter(|r| columns.iter().all(|c| !c[*r].is_nan()))
+          .map(|r| (r, columns.iter().map(|c| c[r]).collect()))
+          .unzip()
+}
+
+fn distance_sq(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+fn nearest(point: &[f64], centroids: &[Vec<f64>]) -> (usize, f64) {
+    centroids.iter()
+             .enumerate()
+             .map(|(c, centroid)| (c, distance_sq(point, centroid)))
+             .fold((0, f64::INFINITY), |best, curr| if curr.1 < best.1 { curr } else { best })
+}
+
+pub struct KMeansResult {
+    pub labels: Vec<i64>,
+    pub iterations: usize,
+    pub inertia: f64
+}
+
+// Lloyd's algorithm with k-means++ initialisation
+pub fn kmeans(points: &[Vec<f64>], k: usize, max_iterations: usize, seed: u64) -> KMeansResult {
+    let mut rng = Rng::new(seed);
+    let k = k.min(points.len()).max(1);
+    if points.is_empty() {
+        return KMeansResult { labels: vec![], iterations: 0, inertia: 0.0 };
+    }
+
+    let mut centroids: Vec<Vec<f64>> = vec![points[rng.below(points.len())].clone()];
+    while centroids.len() < k {
+        let weights: Vec<f64> = points.iter().map(|p| nearest(p, &centroids).1).collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+        let mut pick = rng.next_f64() * total;
+        let next = weights.iter().position(|w| {pick -= w; pick < 0.0}).unwrap_or(points.len() - 1);
+        centroids.push(points[next].clone());
+    }
+
+    let mut labels: Vec<usize> = vec![0; points.len()];
+    let mut iterations = 0;
+    while iterations < max_iterations {
+        iterations += 1;
+        let new_labels: Vec<usize> = points.iter().map(|p| nearest(p, &centroids).0).collect();
+        let changed = new_labels != labels;
+        labels = new_labels;
+
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f64>> = points.iter().zip(&labels).filter(|(_, l)| **l == c).map(|(p, _)| p).collect();
+            // empty clusters keep their centroid
+            if !members.is_empty() {
+                for (d, value) in centroid.iter_mut().enumerate() {
+                    *value = members.iter().map(|m| m[d]).sum::<f64>() / members.len() as f64;
+                }
+            }
+        }
+
+        if !changed && iterations > 1 {
+            break;
+        }
+    }
+
+    let inertia = points.iter().zip(&labels).map(|(p, l)| distance_sq(p, &centroids[*l])).sum();
+    KMeansResult { labels: labels.into_iter().map(|l| l as i64).collect(), iterations, inertia }
+}
+
+// DBSCAN with euclidean distance, points without enough neighbours in reach are NOISE
+pub fn dbscan(points: &[Vec<f64>], epsilon: f64, min_points: usize) -> Vec<i64> {
+    let eps_sq = epsilon * epsilon;
+    let neighbours = |p: usize| -> Vec<usize> {
+        (0..points.len()).filter(|q| distance_sq(&points[p], &points[*q]) <= eps_sq).collect()
+    };
+
+    let mut labels: Vec<Option<i64>> = vec![None; points.len()];
+    let mut cluster: i64 = 0;
+    for p in 0..points.len() {
+        if labels[p].is_some() {
+            continue;
+        }
+        let reach = neighbours(p);
+        if reach.len() < min_points {
+            labels[p] = Some(NOISE);
+            continue;
+        }
+
+        labels[p] = Some(cluster);
+        let mut queue: VecDeque<usize> = reach.into_iter().collect();
+        while let Some(q) = queue.pop_front() {
+            match labels[q] {
+                Some(NOISE) => {labels[q] = Some(cluster);},
+                None => {
+                    labels[q] = Some(cluster);
+                    let reach = neighbours(q);
+                    if reach.len() >= min_points {
+                        queue.extend(reach);
+                    }
+                },
+                _ => {}
+            }
+        }
+        cluster += 1;
+    }
+    labels.into_iter().map(|l| l.unwrap_or(NOISE)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // two well separated blobs of 10 points and a single far away point
+    fn blobs() -> Vec<Vec<f64>> {
+        let mut points: Vec<Vec<f64>> = (0..10).map(|i| vec![(i % 3) as f64 * 0.1, (i / 3) as f64 * 0.1]).collect();
+        points.extend((0..10).map(|i| vec![10.0 + (i % 3) as f64 * 0.1, 10.0 + (i / 3) as f64 * 0.1]));
+        points.push(vec![-20.0, 30.0]);
+        points
+    }
+
+    #[test]
+    fn kmeans_finds_separated_blobs() {
+        let points = blobs()[..20].to_vec();
+        for seed in 0..5 {
+            let result = kmeans(&points, 2, 100, seed);
+            assert!(result.labels[..10].iter().all(|l| *l == result.labels[0]));
+            assert!(result.labels[10..].iter().all(|l| *l == result.labels[10]));
+            assert_ne!(result.labels[0], result.labels[10]);
+        }
+    }
+
+    #[test]
+    fn dbscan_labels_outliers_as_noise() {
+        let labels = dbscan(&blobs(), 0.5, 3);
+        assert!(labels[..10].iter().all(|l| *l == 0));
+        assert!(labels[10..20].iter().all(|l| *l == 1));
+        assert_eq!(labels[20], NOISE);
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use crate::rng::Rng;
+
+
+pub const ALGORITHMS: [&str; 2] = ["Louvain", "LabelPropagation"];
+
+// one level of the Louvain hierarchy, `adjacency[i][i]` holds the weight inside node i counted
+// from both ends so that the degree of a node is the sum of its row
+struct Level {
+    adjacency: Vec<HashMap<usize, f64>>,
+    degrees: Vec<f64>
+}
+
+impl Level {
+    fn new(adjacency: Vec<HashMap<usize, f64>>) -> Level {
+        let degrees = adjacency.iter().map(|row| row.values().sum()).collect();
+        Level { adjacency, degrees }
+    }
+
+    // Move single nodes to the neighbouring community with the largest modularity gain until no
+    // move improves it. Returns the community per node and whether anything moved.
+    fn local_moving(&self, resolution: f64, rng: &mut Rng) -> (Vec<usize>, bool) {
+        let n = self.adjacency.len();
+        let m2: f64 = self.degrees.iter().sum();
+        let mut community: Vec<usize> = (0..n).collect();
+        let mut totals: Vec<f64> = self.degrees.clone();
+        let mut order: Vec<usize> = (0..n).collect();
+        rng.shuffle(&mut order);
+
+        let mut moved_any = false;
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for node in &order {
+                let (node, current) = (*node, community[*node]);
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                self.adjacency[node].iter().filter(|(nb, _)| **nb != node).for_each(|(nb, w)| *links.entry(community[*nb]).or_insert(0.0) += w);
+
+                totals[current] -= self.degrees[node];
+                let gain = |c: usize, totals: &[f64]| links.get(&c).copied().unwrap_or(0.0) - resolution * totals[c] * self.degrees[node] / m2;
+                let stay = gain(current, &totals);
+                let (best, best_gain) = links.keys().map(|c| (*c, gain(*c, &totals))).fold((current, stay), |b, c| if c.1 > b.1 + 1e-12 { c } else { b });
+                totals[best] += self.degrees[node];
+                if best != current && best_gain > stay {
+                    community[node] = best;
+                    moved = true;
+                    moved_any = true;
+                }
+            }
+        }
+        (community, moved_any)
+    }
+
+    fn aggregate(&self, community: &[usize], count: usize) -> Level {
+        let mut adjacency: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        for (node, row) in self.adjacency.iter().enumerate() {
+            for (nb, w) in row {
+                *adjacency[community[node]].entry(community[*nb]).or_insert(0.0) += w;
+            }
+        }
+        Level::new(adjacency)
+    }
+}
+
+// number the communities 0.. by first appearance
+fn compact(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let compacted = labels.iter().map(|l| {
+        let next = ids.len();
+        *ids.entry(*l).or_insert(next)
+    }).collect();
+    (compacted, ids.len())
+}
+
+// Louvain method, communities that fall apart into several components are split afterwards so
+// that every community is connected (as guaranteed by Leiden)
+pub fn louvain(adjacency: &[BTreeMap<usize, f64>], resolution: f64, seed: u64) -> Vec<usize> {
+    let mut rng = Rng::new(seed);
+    let mut level = Level::new(adjacency.iter().map(|row| row.iter().map(|(k, v)| (*k, *v)).collect()).collect());
+    if level.degrees.iter().sum::<f64>() <= 0.0 {
+        return (0..adjacency.len()).collect();
+    }
+
+    let mut membership: Vec<usize> = (0..adjacency.len()).collect();
+    loop {
+        let (community, moved) = level.local_moving(resolution, &mut rng);
+        if !moved {
+            break;
+        }
+        let (community, count) = compact(&community);
+        membership = membership.iter().map(|c| community[*c]).collect();
+        level = level.aggregate(&community, count);
+    }
+    split_disconnected(adjacency, &membership)
+}
+
+// asynchronous label propagation, every node takes the label with the largest weight among its
+// neighbours, ties are broken randomly
+pub fn label_propagation(adjacency: &[BTreeMap<usize, f64>], max_iterations: usize, seed: u64) -> Vec<usize> {
+    let mut rng = Rng::new(seed);
+    let mut labels: Vec<usize> = (0..adjacency.len()).collect();
+    let mut order: Vec<usize> = (0..adjacency.len()).collect();
+    for _ in 0..max_iterations {
+        rng.shuffle(&mut order);
+        let mut changed = false;
+        for node in &order {
+            let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+            adjacency[*node].iter().filter(|(nb, _)| *nb != node).for_each(|(nb, w)| *weights.entry(labels[*nb]).or_insert(0.0) += w);
+            let max = match weights.values().copied().fold(None, |m: Option<f64>, w| Some(m.map_or(w, |m| m.max(w)))) {
+                Some(max) => max,
+                None => continue
+            };
+            let candidates: Vec<usize> = weights.iter().filter(|(_, w)| **w >= max - 1e-12).map(|(l, _)| *l).collect();
+            if !candidates.contains(&labels[*node]) {
+                labels[*node] = candidates[rng.below(candidates.len())];
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    split_disconnected(adjacency, &labels)
+}
+
+// connected parts of every community become communities of their own, numbered by decreasing size
+fn split_disconnected(adjacency: &[BTreeMap<usize, f64>], labels: &[usize]) -> Vec<usize> {
+    let n = adjacency.len();
+    let mut community: Vec<Option<usize>> = vec![None; n];
+    let mut sizes: Vec<usize> = vec![];
+    for start in 0..n {
+        if community[start].is_some() {
+            continue;
+        }
+        let id = sizes.len();
+        sizes.push(0);
+        community[start] = Some(id);
+        let mut queue: VecDeque<usize> = VecDeque::from([start]);
+        while let Some(v) = queue.pop_front() {
+            sizes[id] += 1;
+            for w in adjacency[v].keys() {
+                if community[*w].is_none() && labels[*w] == labels[start] {
+                    community[*w] = Some(id);
+                    queue.push_back(*w);
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|c| (std::cmp::Reverse(sizes[*c]), *c));
+    let mut renumbered: Vec<usize> = vec![0; sizes.len()];
+    order.iter().enumerate().for_each(|(new, old)| renumbered[*old] = new);
+    community.into_iter().map(|c| renumbered[c.expect("every node is reached from its own start")]).collect()
+}
+
+pub fn modularity(adjacency: &[BTreeMap<usize, f64>], community: &[usize], resolution: f64) -> f64 {
+    let m2: f64 = adjacency.iter().flat_map(|row| row.values()).sum();
+    if m2 <= 0.0 {
+        return 0.0;
+    }
+    let count = community.iter().max().map_or(0, |c| c + 1);
+    let mut inside: Vec<f64> = vec![0.0; count];
+    let mut totals: Vec<f64> = vec![0.0; count];
+    for (node, row) in adjacency.iter().enumerate() {
+        for (nb, w) in row {
+            totals[community[node]] += w;
+            if community[node] == community[*nb] {
+                inside[community[node]] += w;
+            }
+        }
+    }
+    inside.iter().zip(&totals).map(|(i, t)| i / m2 - resolution * (t / m2).powi(2)).sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // undirected adjacency with unit weights
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Vec<BTreeMap<usize, f64>> {
+        let mut adjacency: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); n];
+        for (a, b) in edges {
+            adjacency[*a].insert(*b, 1.0);
+            adjacency[*b].insert(*a, 1.0);
+        }
+        adjacency
+    }
+
+    fn cliques(bridge: bool) -> Vec<BTreeMap<usize, f64>> {
+        let mut edges: Vec<(usize, usize)> = vec![];
+        for offset in [0, 4] {
+            for a in 0..4 {
+                for b in a + 1..4 {
+                    edges.push((offset + a, offset + b));
+                }
+            }
+        }
+        if bridge {
+            edges.push((3, 4));
+        }
+        graph(8, &edges)
+    }
+
+    #[test]
+    fn louvain_separates_disjoint_cliques() {
+        for seed in 0..5 {
+            let community = louvain(&cliques(false), 1.0, seed);
+            assert!(community[..4].iter().all(|c| *c == community[0]));
+            assert!(community[4..].iter().all(|c| *c == community[4]));
+            assert_ne!(community[0], community[4]);
+        }
+    }
+
+    #[test]
+    fn louvain_separates_bridged_cliques() {
+        let community = louvain(&cliques(true), 1.0, 42);
+        assert!(community[..4].iter().all(|c| *c == community[0]));
+        assert!(community[4..].iter().all(|c| *c == community[4]));
+        assert_ne!(community[0], community[4]);
+        assert!(modularity(&cliques(true), &community, 1.0) > 0.4);
+    }
+}
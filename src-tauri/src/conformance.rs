@@ -0,0 +1,348 @@
+use std::collections::{HashMap, HashSet, VecDeque, BTreeMap};
+use pmrs::objects::ocel::Ocel;
+use crate::petri_net::{OcPetriNet, flatten_log};
+
+
+// upper bound on the markings explored when searching for silent transition sequences
+const MAX_SILENT_STATES: usize = 256;
+
+type Marking = Vec<u32>;
+// places of a transition paired with the number of arcs between the place and the transition
+type Arcs = Vec<(usize, u32)>;
+
+// the part of an object-centric net that concerns a single object type
+struct ProjectedNet {
+    initial: Marking,
+    final_marking: Marking,
+    // (label, input places, output places), label is None for silent transitions
+    transitions: Vec<(Option<String>, Arcs, Arcs)>
+}
+
+fn multiplicities(places: impl Iterator<Item = usize>) -> Arcs {
+    let mut counts: BTreeMap<usize, u32> = BTreeMap::new();
+    places.for_each(|p| *counts.entry(p).or_insert(0) += 1);
+    counts.into_iter().collect()
+}
+
+fn tokens(arcs: &[(usize, u32)]) -> u32 {
+    arcs.iter().map(|(_, weight)| weight).sum()
+}
+
+impl ProjectedNet {
+    fn new(net: &OcPetriNet, object_type: &str) -> ProjectedNet {
+        let places: HashMap<&str, usize> = net.places.iter()
+                                                     .filter(|p| p.object_type == object_type)
+                                                     .enumerate()
+                                                     .map(|(i, p)| (p.id.as_str(), i))
+                                                     .collect();
+        let mut initial: Marking = vec![0; places.len()];
+        let mut final_marking: Marking = vec![0; places.len()];
+        net.places.iter().filter(|p| p.object_type == object_type).for_each(|p| {
+            if p.initial { initial[places[p.id.as_str()]] = 1; }
+            if p.final_place { final_marking[places[p.id.as_str()]] = 1; }
+        });
+
+        let transitions = net.transitions.iter().filter_map(|t| {
+            let inputs = multiplicities(net.arcs.iter().filter(|a| a.target == t.id && a.object_type == object_type).filter_map(|a| places.get(a.source.as_str())).copied());
+            let outputs = multiplicities(net.arcs.iter().filter(|a| a.source == t.id && a.object_type == object_type).filter_map(|a| places.get(a.target.as_str())).copied());
+            if inputs.is_empty() && outputs.is_empty() {
+                return None;
+            }
+            Some((t.label.clone(), inputs, outputs))
+        }).collect();
+
+        ProjectedNet { initial, final_marking, transitions }
+    }
+
+    fn enabled(&self, marking: &Marking, transition: usize) -> bool {
+        self.transitions[transition].1.iter().all(|(p, weight)| marking[*p] >= *weight)
+    }
+
+    // only called on enabled transitions or after the missing tokens have been added
+    fn fire(&self, marking: &mut Marking, transition: usize) {
+        self.transitions[transition].1.iter().for_each(|(p, weight)| marking[*p] = marking[*p].saturating_sub(*weight));
+        self.transitions[transition].2.iter().for_each(|(p, weight)| marking[*p] += weight);
+    }
+
+    // breadth first search over silent transitions for a marking satisfying `goal`,
+    // returns the silent transitions to fire
+    fn silent_path<F: Fn(&Marking) -> bool>(&self, marking: &Marking, goal: F) -> Option<Vec<usize>> {
+        let mut queue: VecDeque<(Marking, Vec<usize>)> = VecDeque::from(vec![(marking.clone(), vec![])]);
+        let mut seen: HashSet<Marking> = HashSet::new();
+        seen.insert(marking.clone());
+
+        while let Some((curr, path)) = queue.pop_front() {
+            if goal(&curr) {
+                return Some(path);
+            }
+            if seen.len() > MAX_SILENT_STATES {
+                break;
+            }
+            for (t, (label, _, _)) in self.transitions.iter().enumerate() {
+                if label.is_none() && self.enabled(&curr, t) {
+                    let mut next = curr.clone();
+                    self.fire(&mut next, t);
+                    if seen.insert(next.clone()) {
+                        let mut next_path = path.clone();
+                        next_path.push(t);
+                        queue.push_back((next, next_path));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // visible labels that can fire after firing silent transitions only
+    fn enabled_labels(&self, marking: &Marking) -> HashSet<&str> {
+        let mut labels: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<Marking> = VecDeque::from(vec![marking.clone()]);
+        let mut seen: HashSet<Marking> = HashSet::new();
+        seen.insert(marking.clone());
+
+        while let Some(curr) = queue.pop_front() {
+            for (t, (label, _, _)) in self.transitions.iter().enumerate() {
+                if !self.enabled(&curr, t) {
+                    continue;
+                }
+                match label {
+                    Some(l) => {labels.insert(l.as_str());},
+                    None if seen.len() <= MAX_SILENT_STATES => {
+                        let mut next = curr.clone();
+                        self.fire(&mut next, t);
+                        if seen.insert(next.clone()) {
+                            queue.push_back(next);
+                        }
+                    },
+                    None => {}
+                }
+            }
+        }
+        labels
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReplayResult {
+    pub object: String,
+    pub object_type: String,
+    pub produced: u32,
+    pub consumed: u32,
+    pub missing: u32,
+    pub remaining: u32,
+    pub deviating: Vec<String>
+}
+
+impl ReplayResult {
+    pub fn fitness(&self) -> f64 {
+        let missing = if self.consumed == 0 { 0.0 } else { self.missing as f64 / self.consumed as f64 };
+        let remaining = if self.produced == 0 { 0.0 } else { self.remaining as f64 / self.produced as f64 };
+        0.5 * (1.0 - missing) + 0.5 * (1.0 - remaining)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ObjectTypeConformance {
+    pub fitness: f64,
+    pub precision: f64,
+    pub objects: usize
+}
+
+// labels the model allows after a prefix, labels the log continues the prefix with and how often
+// the prefix occurs
+type PrefixBehaviour = (HashSet<String>, HashSet<String>, u32);
+
+fn replay_trace(net: &ProjectedNet, trace: &[&str], prefixes: &mut HashMap<String, PrefixBehaviour>) -> ReplayResult {
+    let mut result = ReplayResult::default();
+    let mut marking = net.initial.clone();
+    result.produced = marking.iter().sum();
+    let mut prefix = String::new();
+
+    for act in trace {
+        // remember what the model allows and what the log does after every prefix for precision
+        let entry = prefixes.entry(prefix.to_string()).or_insert_with(|| (net.enabled_labels(&marking).into_iter().map(|l| l.to_string()).collect(), HashSet::new(), 0));
+        entry.1.insert(act.to_string());
+        entry.2 += 1;
+        prefix.push_str(act);
+        prefix.push('\u{1f}');
+
+        let candidates: Vec<usize> = (0..net.transitions.len()).filter(|t| net.transitions[*t].0.as_deref() == Some(*act)).collect();
+        if candidates.is_empty() {
+            result.deviating.push(act.to_string());
+            continue;
+        }
+
+        let transition = match candidates.iter().find(|t| net.enabled(&marking, **t)) {
+            Some(t) => *t,
+            None => {
+                match candidates.iter().find_map(|t| net.silent_path(&marking, |m| net.enabled(m, *t)).map(|path| (*t, path))) {
+                    Some((t, path)) => {
+                        path.iter().for_each(|silent| {
+                            result.consumed += tokens(&net.transitions[*silent].1);
+                            result.produced += tokens(&net.transitions[*silent].2);
+                            net.fire(&mut marking, *silent);
+                        });
+                        t
+                    },
+                    None => {
+                        // force the transition by adding every token it misses
+                        let t = candidates[0];
+                        result.deviating.push(act.to_string());
+                        net.transitions[t].1.iter().for_each(|(p, weight)| {
+                            if marking[*p] < *weight {
+                                result.missing += weight - marking[*p];
+                                marking[*p] = *weight;
+                            }
+                        });
+                        t
+                    }
+                }
+            }
+        };
+
+        result.consumed += tokens(&net.transitions[transition].1);
+        result.produced += tokens(&net.transitions[transition].2);
+        net.fire(&mut marking, transition);
+    }
+
+    // move towards the final marking with silent transitions and consume it
+    let final_marking = &net.final_marking;
+    if let Some(path) = net.silent_path(&marking, |m| m.iter().zip(final_marking).all(|(have, need)| have >= need)) {
+        path.iter().for_each(|silent| {
+            result.consumed += tokens(&net.transitions[*silent].1);
+            result.produced += tokens(&net.transitions[*silent].2);
+            net.fire(&mut marking, *silent);
+        });
+    }
+    for (have, need) in marking.iter_mut().zip(final_marking) {
+        result.consumed += need;
+        if *have < *need {
+            result.missing += need - *have;
+            *have = 0;
+        } else {
+            *have -= need;
+        }
+    }
+    result.remaining = marking.iter().sum();
+    result.deviating.sort();
+    result.deviating.dedup();
+    result
+}
+
+// share of the labels allowed after a prefix that the log uses, every prefix is weighted with
+// the number of objects that pass it
+fn precision(prefixes: &HashMap<String, PrefixBehaviour>) -> f64 {
+    let (allowed, used) = prefixes.values().fold((0, 0), |(allowed, used), (enabled, observed, count)| {
+        (allowed + *count as usize * enabled.len(), used + *count as usize * enabled.intersection(observed).count())
+    });
+    if allowed == 0 { 1.0 } else { used as f64 / allowed as f64 }
+}
+
+// Token-based replay of every object on the net of its object type. Returns the replay result
+// for every object and the aggregated fitness and (escaping edges) precision per object type.
+pub fn token_replay(log: &Ocel, net: &OcPetriNet) -> (Vec<ReplayResult>, BTreeMap<String, ObjectTypeConformance>) {
+    let mut results: Vec<ReplayResult> = vec![];
+    let mut summary: BTreeMap<String, ObjectTypeConformance> = BTreeMap::new();
+
+    for ot in net.object_types() {
+        let projected = ProjectedNet::new(net, ot);
+        let mut prefixes: HashMap<String, PrefixBehaviour> = HashMap::new();
+        let (mut missing, mut consumed, mut remaining, mut produced) = (0, 0, 0, 0);
+
+        let traces = flatten_log(log, ot);
+        for (oid, trace) in &traces {
+            let mut result = replay_trace(&projected, trace, &mut prefixes);
+            result.object = log.object_map.get_by_right(oid).map_or(oid.to_string(), |name| name.to_string());
+            result.object_type = ot.to_string();
+            missing += result.missing;
+            consumed += result.consumed;
+            remaining += result.remaining;
+            produced += result.produced;
+            results.push(result);
+        }
+
+        let fitness = 0.5 * (1.0 - if consumed == 0 { 0.0 } else { missing as f64 / consumed as f64 }) + 0.5 * (1.0 - if produced == 0 { 0.0 } else { remaining as f64 / produced as f64 });
+        let precision = precision(&prefixes);
+        summary.insert(ot.to_string(), ObjectTypeConformance { fitness, precision, objects: traces.len() });
+    }
+
+    (results, summary)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // p0 -a-> p1 -tau-> p2 -b-> p3
+    fn sequence() -> ProjectedNet {
+        ProjectedNet {
+            initial: vec![1, 0, 0, 0],
+            final_marking: vec![0, 0, 0, 1],
+            transitions: vec![(Some("a".to_string()), vec![(0, 1)], vec![(1, 1)]),
+                              (None, vec![(1, 1)], vec![(2, 1)]),
+                              (Some("b".to_string()), vec![(2, 1)], vec![(3, 1)])]
+        }
+    }
+
+    #[test]
+    fn perfectly_fitting_trace() {
+        let result = replay_trace(&sequence(), &["a", "b"], &mut HashMap::new());
+        assert_eq!(result.missing, 0);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(result.produced, result.consumed);
+        assert!(result.deviating.is_empty());
+        assert_eq!(result.fitness(), 1.0);
+    }
+
+    // p0 =2=> a -> p1
+    fn double_arc() -> ProjectedNet {
+        ProjectedNet {
+            initial: vec![1, 0],
+            final_marking: vec![0, 1],
+            transitions: vec![(Some("a".to_string()), vec![(0, 2)], vec![(1, 1)])]
+        }
+    }
+
+    #[test]
+    fn duplicate_arcs_need_all_their_tokens() {
+        let net = double_arc();
+        assert!(!net.enabled(&vec![1, 0], 0));
+        assert!(net.enabled(&vec![2, 0], 0));
+
+        let result = replay_trace(&net, &["a"], &mut HashMap::new());
+        assert_eq!(result.missing, 1);
+        assert_eq!(result.consumed, 3);
+        assert_eq!(result.produced, 2);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(result.deviating, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn forced_fire_adds_every_missing_token() {
+        // the second a finds p0 empty and misses both of its tokens
+        let result = replay_trace(&double_arc(), &["a", "a"], &mut HashMap::new());
+        assert_eq!(result.missing, 3);
+        assert_eq!(result.consumed, 5);
+        assert_eq!(result.produced, 3);
+        assert_eq!(result.remaining, 1);
+    }
+
+    #[test]
+    fn precision_weights_prefixes_by_frequency() {
+        let labels = |labels: &[&str]| labels.iter().map(|l| l.to_string()).collect::<HashSet<String>>();
+        let mut prefixes: HashMap<String, PrefixBehaviour> = HashMap::new();
+        prefixes.insert(String::new(), (labels(&["a"]), labels(&["a"]), 9));
+        prefixes.insert("a".to_string(), (labels(&["b", "c"]), labels(&["b"]), 1));
+        assert_eq!(precision(&prefixes), 10.0 / 11.0);
+        assert_eq!(precision(&HashMap::new()), 1.0);
+    }
+
+    #[test]
+    fn deviating_trace() {
+        let result = replay_trace(&sequence(), &["b", "c"], &mut HashMap::new());
+        assert_eq!(result.deviating, vec!["b".to_string(), "c".to_string()]);
+        assert!(result.missing > 0);
+        assert!(result.fitness() < 1.0);
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, BTreeMap};
+use pmrs::objects::ocel::Ocel;
+use serde::{Serialize, Deserialize};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DfgEdge {
+    pub source: String,
+    pub target: String,
+    pub object_type: String,
+    pub frequency: u32,
+    pub mean_duration: f64,
+    pub min_duration: f64,
+    pub max_duration: f64
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcDfg {
+    // activity -> number of events
+    pub activities: BTreeMap<String, u32>,
+    // object type -> activity -> number of objects starting / ending with that activity
+    pub start_activities: BTreeMap<String, BTreeMap<String, u32>>,
+    pub end_activities: BTreeMap<String, BTreeMap<String, u32>>,
+    pub edges: Vec<DfgEdge>
+}
+
+impl OcDfg {
+    pub fn object_types(&self) -> Vec<&str> {
+        self.start_activities.keys().map(|ot| ot.as_str()).collect()
+    }
+
+    // remove every edge (and start / end arc) occurring less than min_frequency times
+    pub fn filter_frequency(&mut self, min_frequency: u32) {
+        self.edges.retain(|e| e.frequency >= min_frequency);
+        for arcs in self.start_activities.values_mut().chain(self.end_activities.values_mut()) {
+            arcs.retain(|_, freq| *freq >= min_frequency);
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph ocdfg {\n    rankdir=LR;\n");
+        let colours: HashMap<&str, &str> = self.object_types().into_iter().enumerate().map(|(i, ot)| (ot, PALETTE[i % PALETTE.len()])).collect();
+        let node_ids: HashMap<&str, usize> = self.activities.keys().enumerate().map(|(i, act)| (act.as_str(), i)).collect();
+
+        for (act, freq) in &self.activities {
+            dot.push_str(&format!("    a{} [shape=box, label=\"{} ({})\"];\n", node_ids[act.as_str()], escape(act), freq));
+        }
+
+        for ot in self.object_types() {
+            let colour = colours[ot];
+            dot.push_str(&format!("    \"start:{}\" [shape=circle, style=filled, fillcolor=\"{}\", label=\"{}\"];\n", escape(ot), colour, escape(ot)));
+            dot.push_str(&format!("    \"end:{}\" [shape=doublecircle, style=filled, fillcolor=\"{}\", label=\"\"];\n", escape(ot), colour));
+            for (act, freq) in &self.start_activities[ot] {
+                dot.push_str(&format!("    \"start:{}\" -> a{} [color=\"{}\", label=\"{}\"];\n", escape(ot), node_ids[act.as_str()], colour, freq));
+            }
+            for (act, freq) in &self.end_activities[ot] {
+                dot.push_str(&format!("    a{} -> \"end:{}\" [color=\"{}\", label=\"{}\"];\n", node_ids[act.as_str()], escape(ot), colour, freq));
+            }
+        }
+
+        for e in &self.edges {
+            dot.push_str(&format!("    a{} -> a{} [color=\"{}\", label=\"{} ({:.1}s)\"];\n", node_ids[e.source.as_str()], node_ids[e.target.as_str()], colours[e.object_type.as_str()], e.frequency, e.mean_duration));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// colours used to distinguish object types in the DOT exports
+pub const PALETTE: [&str; 10] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf"];
+
+pub fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Flatten the log per object type, compute a directly-follows graph on each flattened log
+// and merge them into one multigraph on the shared activities.
+pub fn discover_ocdfg(log: &Ocel) -> OcDfg {
+    let mut activities: BTreeMap<String, u32> = log.activities.iter().map(|act| (act.to_string(), 0)).collect();
+    log.events.values().for_each(|ev| *activities.entry(ev.activity.to_string()).or_insert(0) += 1);
+
+    let mut start_activities: BTreeMap<String, BTreeMap<String, u32>> = BTreeMap::new();
+    let mut end_activities: BTreeMap<String, BTreeMap<String, u32>> = BTreeMap::new();
+    let mut durations: BTreeMap<(String, String, String), Vec<f64>> = BTreeMap::new();
+
+    for obj in log.objects.values() {
+        let mut trace: Vec<usize> = obj.events.iter().filter(|eid| log.events.contains_key(eid)).copied().collect();
+        if trace.is_empty() {
+            continue;
+        }
+        trace.sort_by_key(|eid| (log.events[eid].timestamp, *eid));
+
+        let first = &log.events[&trace[0]].activity;
+        let last = &log.events[&trace[trace.len() - 1]].activity;
+        *start_activities.entry(obj.obj_type.to_string()).or_default().entry(first.to_string()).or_insert(0) += 1;
+        *end_activities.entry(obj.obj_type.to_string()).or_default().entry(last.to_string()).or_insert(0) += 1;
+
+        trace.windows(2).for_each(|pair| {
+            let (src, tar) = (&log.events[&pair[0]], &log.events[&pair[1]]);
+            durations.entry((obj.obj_type.to_string(), src.activity.to_string(), tar.activity.to_string()))
+                     .or_default()
+                     .push((tar.timestamp - src.timestamp).num_milliseconds() as f64 / 1000.0);
+        });
+    }
+
+    let edges: Vec<DfgEdge> = durations.into_iter().map(|((object_type, source, target), times)| {
+        DfgEdge {
+            source,
+            target,
+            object_type,
+            frequency: times.len() as u32,
+            mean_duration: times.iter().sum::<f64>() / times.len() as f64,
+            min_duration: times.iter().cloned().fold(f64::INFINITY, f64::min),
+            max_duration: times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+        }
+    }).collect();
+
+    OcDfg { activities, start_activities, end_activities, edges }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::fixtures::{event, object, log};
+
+    // two orders place -> pay, one item place -> pick -> pay
+    fn orders() -> Ocel {
+        log(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1", "i1"], json!({})),
+                 event("e2", "pick", "2022-01-01T10:01:00+00:00", &["i1"], json!({})),
+                 event("e3", "pay", "2022-01-01T10:02:00+00:00", &["o1", "i1"], json!({})),
+                 event("e4", "place", "2022-01-02T10:00:00+00:00", &["o2"], json!({})),
+                 event("e5", "pay", "2022-01-02T10:04:00+00:00", &["o2"], json!({}))],
+            vec![object("o1", "order", json!({})),
+                 object("o2", "order", json!({})),
+                 object("i1", "item", json!({}))])
+    }
+
+    fn edge<'a>(dfg: &'a OcDfg, object_type: &str, source: &str, target: &str) -> Option<&'a DfgEdge> {
+        dfg.edges.iter().find(|e| e.object_type == object_type && e.source == source && e.target == target)
+    }
+
+    #[test]
+    fn edges_are_discovered_per_object_type() {
+        let dfg = discover_ocdfg(&orders());
+        assert_eq!(dfg.activities, BTreeMap::from([("pay".to_string(), 2), ("pick".to_string(), 1), ("place".to_string(), 2)]));
+        assert_eq!(dfg.object_types(), vec!["item", "order"]);
+        assert_eq!(dfg.start_activities["order"], BTreeMap::from([("place".to_string(), 2)]));
+        assert_eq!(dfg.end_activities["item"], BTreeMap::from([("pay".to_string(), 1)]));
+        assert_eq!(dfg.edges.len(), 3);
+
+        let order_pay = edge(&dfg, "order", "place", "pay").unwrap();
+        assert_eq!(order_pay.frequency, 2);
+        assert_eq!(order_pay.mean_duration, 180.0);
+        assert_eq!(order_pay.min_duration, 120.0);
+        assert_eq!(order_pay.max_duration, 240.0);
+        assert_eq!(edge(&dfg, "item", "place", "pick").unwrap().mean_duration, 60.0);
+        assert!(edge(&dfg, "item", "place", "pay").is_none());
+    }
+
+    #[test]
+    fn filter_frequency_drops_rare_edges_and_arcs() {
+        let mut dfg = discover_ocdfg(&orders());
+        dfg.filter_frequency(2);
+        assert_eq!(dfg.edges.len(), 1);
+        assert!(edge(&dfg, "order", "place", "pay").is_some());
+        assert!(dfg.start_activities["item"].is_empty());
+        assert_eq!(dfg.start_activities["order"]["place"], 2);
+        // activities are kept even if all their edges are gone
+        assert_eq!(dfg.activities.len(), 3);
+    }
+}
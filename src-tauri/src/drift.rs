@@ -0,0 +1,186 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use chrono::{DateTime, Duration, Utc};
+use pmrs::objects::ocel::Ocel;
+use crate::numeric::chi_squared_sf;
+
+
+pub const SIGNATURES: [&str; 3] = ["Activities", "DirectlyFollows", "Interactions"];
+
+pub struct Window {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub events: Vec<usize>
+}
+
+// Windows of the given width from the first event on, every `step` a new window starts. The last
+// window contains the last event.
+pub fn windows(log: &Ocel, width: Duration, step: Duration) -> Result<Vec<Window>, String> {
+    if width <= Duration::zero() || step <= Duration::zero() {
+        return Err("Window size and step have to be positive".to_string());
+    }
+    let (first, last) = match (log.events.values().map(|ev| ev.timestamp).min(), log.events.values().map(|ev| ev.timestamp).max()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {return Err("The log has no events".to_string());}
+    };
+    let count = (last - first).num_milliseconds() / step.num_milliseconds().max(1) + 1;
+    if count > 10_000 {
+        return Err("The step is too small, more than 10000 windows would be created".to_string());
+    }
+
+    let mut result: Vec<Window> = (0..count).map(|i| {
+        let start = first + step * i as i32;
+        Window { start, end: start + width, events: vec![] }
+    }).collect();
+    for (eid, ev) in log.events.iter() {
+        // only the windows starting within `width` before the event can contain it
+        let latest = ((ev.timestamp - first).num_milliseconds() / step.num_milliseconds().max(1)) as usize;
+        for window in result.iter_mut().take(latest + 1).rev() {
+            if ev.timestamp >= window.end {
+                break;
+            }
+            window.events.push(*eid);
+        }
+    }
+    result.iter_mut().for_each(|w| w.events.sort_by_key(|eid| (log.events[eid].timestamp, *eid)));
+    Ok(result)
+}
+
+// Counts that describe the behaviour in a window: activity frequencies, directly-follows
+// relations per object type (within the window) or the object type pairs that share events.
+pub fn signature(log: &Ocel, events: &[usize], kind: &str) -> BTreeMap<String, f64> {
+    let mut counts: BTreeMap<String, f64> = BTreeMap::new();
+    match kind {
+        "DirectlyFollows" => {
+            // events are sorted by time, so the traces are as well
+            let mut traces: HashMap<usize, Vec<usize>> = HashMap::new();
+            events.iter().for_each(|eid| log.events[eid].omap.iter().for_each(|oid| traces.entry(*oid).or_default().push(*eid)));
+            for (oid, trace) in traces {
+                let obj_type = log.objects.get(&oid).map_or("", |obj| obj.obj_type.as_str());
+                trace.windows(2).for_each(|pair| {
+                    *counts.entry(format!("{}: {} -> {}", obj_type, log.events[&pair[0]].activity, log.events[&pair[1]].activity)).or_insert(0.0) += 1.0;
+                });
+            }
+        },
+        "Interactions" => {
+            for eid in events {
+                let mut types: BTreeMap<&str, f64> = BTreeMap::new();
+                log.events[eid].omap.iter().filter_map(|oid| log.objects.get(oid)).for_each(|obj| *types.entry(obj.obj_type.as_str()).or_insert(0.0) += 1.0);
+                let types: Vec<(&str, f64)> = types.into_iter().collect();
+                for (i, (ot1, c1)) in types.iter().enumerate() {
+                    if *c1 > 1.0 {
+                        *counts.entry(format!("{}|{}", ot1, ot1)).or_insert(0.0) += c1 * (c1 - 1.0) / 2.0;
+                    }
+                    for (ot2, c2) in types.iter().skip(i + 1) {
+                        *counts.entry(format!("{}|{}", ot1, ot2)).or_insert(0.0) += c1 * c2;
+                    }
+                }
+            }
+        },
+        _ => events.iter().for_each(|eid| *counts.entry(log.events[eid].activity.to_string()).or_insert(0.0) += 1.0)
+    }
+    counts
+}
+
+pub struct Comparison {
+    // total variation distance between the relative frequencies
+    pub distance: f64,
+    pub statistic: f64,
+    pub p_value: f64
+}
+
+// Chi-squared test of homogeneity between the signatures of two windows. None if one of them is
+// empty or they only share a single category.
+pub fn compare(a: &BTreeMap<String, f64>, b: &BTreeMap<String, f64>) -> Option<Comparison> {
+    let (total_a, total_b): (f64, f64) = (a.values().sum(), b.values().sum());
+    if total_a <= 0.0 || total_b <= 0.0 {
+        return None;
+    }
+    let keys: HashSet<&String> = a.keys().chain(b.keys()).collect();
+    if keys.len() < 2 {
+        return None;
+    }
+
+    let total = total_a + total_b;
+    let mut statistic = 0.0;
+    let mut distance = 0.0;
+    for key in &keys {
+        let (ca, cb) = (a.get(*key).copied().unwrap_or(0.0), b.get(*key).copied().unwrap_or(0.0));
+        let (ea, eb) = (total_a * (ca + cb) / total, total_b * (ca + cb) / total);
+        statistic += (ca - ea).powi(2) / ea + (cb - eb).powi(2) / eb;
+        distance += (ca / total_a - cb / total_b).abs();
+    }
+    Some(Comparison { distance: distance / 2.0, statistic, p_value: chi_squared_sf(statistic, keys.len() - 1) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::fixtures::{event, object, log, eid};
+
+    fn counts(entries: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        entries.iter().map(|(key, count)| (key.to_string(), *count)).collect()
+    }
+
+    fn close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn compare_two_categories() {
+        // expected counts are 15 everywhere, statistic 4 * 25 / 15 with one degree of freedom
+        let comparison = compare(&counts(&[("a", 10.0), ("b", 20.0)]), &counts(&[("a", 20.0), ("b", 10.0)])).unwrap();
+        close(comparison.statistic, 20.0 / 3.0);
+        close(comparison.p_value, 0.009_823_275);
+        close(comparison.distance, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn compare_missing_categories_count_as_zero() {
+        // expected counts 15/10/5 per window, statistic 10 + 10 / 3 with two degrees of freedom
+        let comparison = compare(&counts(&[("a", 10.0), ("b", 10.0), ("c", 10.0)]), &counts(&[("a", 20.0), ("b", 10.0)])).unwrap();
+        close(comparison.statistic, 40.0 / 3.0);
+        close(comparison.p_value, (-20.0f64 / 3.0).exp());
+        close(comparison.distance, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn compare_identical_distributions() {
+        let comparison = compare(&counts(&[("a", 1.0), ("b", 3.0)]), &counts(&[("a", 10.0), ("b", 30.0)])).unwrap();
+        close(comparison.statistic, 0.0);
+        close(comparison.p_value, 1.0);
+        close(comparison.distance, 0.0);
+    }
+
+    #[test]
+    fn compare_needs_two_categories_and_counts() {
+        assert!(compare(&counts(&[("a", 3.0)]), &counts(&[("a", 5.0)])).is_none());
+        assert!(compare(&counts(&[]), &counts(&[("a", 5.0), ("b", 1.0)])).is_none());
+    }
+
+    #[test]
+    fn windows_overlap_and_contain_the_last_event() {
+        let log = log(vec![event("e1", "a", "2022-01-01T00:00:00+00:00", &["o1"], json!({})),
+                           event("e2", "b", "2022-01-01T01:30:00+00:00", &["o1"], json!({})),
+                           event("e3", "a", "2022-01-01T03:00:00+00:00", &["o1"], json!({}))],
+                      vec![object("o1", "order", json!({}))]);
+        let windows = windows(&log, Duration::hours(2), Duration::hours(1)).unwrap();
+        let events: Vec<Vec<usize>> = windows.iter().map(|w| w.events.clone()).collect();
+        let (e1, e2, e3) = (eid(&log, "e1"), eid(&log, "e2"), eid(&log, "e3"));
+        assert_eq!(events, vec![vec![e1, e2], vec![e2], vec![e3], vec![e3]]);
+        assert!(windows(&log, Duration::zero(), Duration::hours(1)).is_err());
+    }
+
+    #[test]
+    fn directly_follows_signature_per_object_type() {
+        let log = log(vec![event("e1", "place", "2022-01-01T00:00:00+00:00", &["o1", "i1"], json!({})),
+                           event("e2", "pick", "2022-01-01T01:00:00+00:00", &["i1"], json!({})),
+                           event("e3", "ship", "2022-01-01T02:00:00+00:00", &["o1", "i1"], json!({}))],
+                      vec![object("o1", "order", json!({})), object("i1", "item", json!({}))]);
+        let events = vec![eid(&log, "e1"), eid(&log, "e2"), eid(&log, "e3")];
+        assert_eq!(signature(&log, &events, "DirectlyFollows"),
+                   counts(&[("item: place -> pick", 1.0), ("item: pick -> ship", 1.0), ("order: place -> ship", 1.0)]));
+        assert_eq!(signature(&log, &events, "Interactions"), counts(&[("item|order", 2.0)]));
+        assert_eq!(signature(&log, &events, "Activities"), counts(&[("pick", 1.0), ("place", 1.0), ("ship", 1.0)]));
+    }
+}
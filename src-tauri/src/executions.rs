@@ -0,0 +1,336 @@
+use std::collections::{HashMap, HashSet, BTreeMap};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use chrono::{DateTime, Utc};
+use pmrs::objects::ocel::Ocel;
+
+
+// Executions larger than this, or whose isomorphism test needs more than MAX_ISOMORPHISM_STEPS
+// candidate mappings, are only compared by their Weisfeiler-Lehman signature.
+const MAX_EXACT_ISOMORPHISM: usize = 50;
+const MAX_ISOMORPHISM_STEPS: usize = 100_000;
+const WL_ITERATIONS: usize = 3;
+
+// a process execution: a connected set of objects together with all of their events
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub objects: Vec<usize>,
+    pub events: Vec<usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub executions: Vec<usize>,
+    pub events: usize,
+    pub objects: usize,
+    // some executions were only matched by their WL signature
+    pub approximate: bool
+}
+
+// event graph of an execution: nodes are labelled with activities, edges connect events that
+// directly follow each other for a shared object and are labelled with the object type
+struct ExecutionGraph {
+    labels: Vec<u64>,
+    edges: Vec<(usize, usize, u64)>
+}
+
+fn hash_of<T: Hash>(item: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn union_find_components(n: usize, edges: impl Iterator<Item = (usize, usize)>) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..n).collect();
+    fn root(parent: &mut [usize], i: usize) -> usize {
+        let mut r = i;
+        while parent[r] != r {
+            parent[r] = parent[parent[r]];
+            r = parent[r];
+        }
+        r
+    }
+
+    for (a, b) in edges {
+        let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+        if ra != rb {
+            parent[ra] = rb;
+        }
+    }
+
+    let mut components: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..n {
+        let r = root(&mut parent, i);
+        components.entry(r).or_default().push(i);
+    }
+    components.into_values().collect()
+}
+
+fn executions_from_objects(log: &Ocel, components: Vec<Vec<usize>>) -> Vec<Execution> {
+    let mut executions = components.into_iter().filter_map(|mut objects| {
+        objects.sort_unstable();
+        // references to events that are not in the log are skipped
+        let mut events: Vec<(DateTime<Utc>, usize)> = objects.iter()
+                                                             .filter_map(|oid| log.objects.get(oid))
+                                                             .flat_map(|obj| obj.events.iter().copied())
+                                                             .collect::<HashSet<usize>>()
+                                                             .into_iter()
+                                                             .filter_map(|eid| log.events.get(&eid).map(|ev| (ev.timestamp, eid)))
+                                                             .collect();
+        if events.is_empty() {
+            return None;
+        }
+        events.sort_unstable();
+        Some((events[0], Execution { objects, events: events.into_iter().map(|(_, eid)| eid).collect() }))
+    }).collect::<Vec<((DateTime<Utc>, usize), Execution)>>();
+    executions.sort_by_key(|(first, _)| *first);
+    executions.into_iter().map(|(_, execution)| execution).collect()
+}
+
+// connected components of objects that share at least one event
+pub fn executions_by_shared_events(log: &Ocel) -> Vec<Execution> {
+    let edges = log.events.values().flat_map(|ev| {
+        let objects: Vec<usize> = ev.omap.iter().copied().collect();
+        objects.windows(2).map(|pair| (pair[0], pair[1])).collect::<Vec<(usize, usize)>>()
+    });
+    executions_from_objects(log, union_find_components(log.objects.len(), edges))
+}
+
+// connected components of the objects in the given (undirected) object graph
+pub fn executions_by_object_graph(log: &Ocel, object_edges: &[(usize, usize)]) -> Vec<Execution> {
+    executions_from_objects(log, union_find_components(log.objects.len(), object_edges.iter().copied()))
+}
+
+fn execution_graph(log: &Ocel, execution: &Execution) -> ExecutionGraph {
+    // events and objects that are not in the log are skipped
+    let events: Vec<(usize, &str)> = execution.events.iter().filter_map(|eid| log.events.get(eid).map(|ev| (*eid, ev.activity.as_str()))).collect();
+    let index: HashMap<usize, usize> = events.iter().enumerate().map(|(i, (eid, _))| (*eid, i)).collect();
+    let labels: Vec<u64> = events.iter().map(|(_, activity)| hash_of(activity)).collect();
+    let mut edges: HashSet<(usize, usize, u64)> = HashSet::new();
+
+    for obj in execution.objects.iter().filter_map(|oid| log.objects.get(oid)) {
+        let ot = hash_of(&obj.obj_type);
+        let mut trace: Vec<usize> = obj.events.iter().filter_map(|eid| index.get(eid)).copied().collect();
+        // execution events are sorted by time, so the indices are as well
+        trace.sort_unstable();
+        trace.windows(2).for_each(|pair| {edges.insert((pair[0], pair[1], ot));});
+    }
+
+    let mut edges: Vec<(usize, usize, u64)> = edges.into_iter().collect();
+    edges.sort_unstable();
+    ExecutionGraph { labels, edges }
+}
+
+fn wl_colours(graph: &ExecutionGraph) -> Vec<u64> {
+    let mut colours = graph.labels.clone();
+    for _ in 0..WL_ITERATIONS {
+        let mut outgoing: Vec<Vec<(u64, u64)>> = vec![vec![]; colours.len()];
+        let mut incoming: Vec<Vec<(u64, u64)>> = vec![vec![]; colours.len()];
+        graph.edges.iter().for_each(|(a, b, l)| {
+            outgoing[*a].push((*l, colours[*b]));
+            incoming[*b].push((*l, colours[*a]));
+        });
+        colours = (0..colours.len()).map(|v| {
+            outgoing[v].sort_unstable();
+            incoming[v].sort_unstable();
+            hash_of(&(colours[v], &outgoing[v], &incoming[v]))
+        }).collect();
+    }
+    colours
+}
+
+fn signature(graph: &ExecutionGraph, colours: &[u64]) -> u64 {
+    let mut sorted = colours.to_vec();
+    sorted.sort_unstable();
+    hash_of(&(graph.labels.len(), graph.edges.len(), sorted))
+}
+
+// Backtracking isomorphism test on graphs that already share the WL signature. Returns None if
+// the graphs are too large or the test runs out of steps.
+fn is_isomorphic(g1: &ExecutionGraph, c1: &[u64], g2: &ExecutionGraph, c2: &[u64]) -> Option<bool> {
+    if g1.labels.len() != g2.labels.len() || g1.edges.len() != g2.edges.len() {
+        return Some(false);
+    }
+    if g1.labels.len() > MAX_EXACT_ISOMORPHISM {
+        return None;
+    }
+
+    let edges2: HashSet<(usize, usize, u64)> = g2.edges.iter().copied().collect();
+    let mut adjacent1: Vec<Vec<(usize, usize, u64)>> = vec![vec![]; g1.labels.len()];
+    g1.edges.iter().for_each(|e| {adjacent1[e.0].push(*e); adjacent1[e.1].push(*e);});
+
+    struct Search<'a> {
+        c1: &'a [u64],
+        c2: &'a [u64],
+        adjacent1: &'a [Vec<(usize, usize, u64)>],
+        edges2: &'a HashSet<(usize, usize, u64)>,
+        mapping: Vec<Option<usize>>,
+        used: Vec<bool>,
+        steps: usize
+    }
+
+    impl Search<'_> {
+        fn extend(&mut self, v: usize) -> Option<bool> {
+            if v == self.mapping.len() {
+                return Some(true);
+            }
+            for w in 0..self.c2.len() {
+                if self.used[w] || self.c1[v] != self.c2[w] {
+                    continue;
+                }
+                self.steps += 1;
+                if self.steps > MAX_ISOMORPHISM_STEPS {
+                    return None;
+                }
+                // every edge towards an already mapped node has to exist in the other graph
+                let mapping = &self.mapping;
+                let consistent = self.adjacent1[v].iter().all(|(a, b, l)| {
+                    let (ma, mb) = (if *a == v { Some(w) } else { mapping[*a] }, if *b == v { Some(w) } else { mapping[*b] });
+                    match (ma, mb) {
+                        (Some(ma), Some(mb)) => self.edges2.contains(&(ma, mb, *l)),
+                        _ => true
+                    }
+                });
+                if consistent {
+                    self.mapping[v] = Some(w);
+                    self.used[w] = true;
+                    if self.extend(v + 1)? {
+                        return Some(true);
+                    }
+                    self.mapping[v] = None;
+                    self.used[w] = false;
+                }
+            }
+            Some(false)
+        }
+    }
+
+    let mut search = Search { c1, c2, adjacent1: &adjacent1, edges2: &edges2, mapping: vec![None; g1.labels.len()], used: vec![false; g2.labels.len()], steps: 0 };
+    search.extend(0)
+}
+
+// Group executions into variants, two executions are the same variant if their event graphs are
+// isomorphic (or share their WL signature if that cannot be decided). Variants are sorted by frequency.
+pub fn execution_variants(log: &Ocel, executions: &[Execution]) -> Vec<Variant> {
+    let graphs: Vec<(ExecutionGraph, Vec<u64>)> = executions.iter().map(|ex| {
+        let graph = execution_graph(log, ex);
+        let colours = wl_colours(&graph);
+        (graph, colours)
+    }).collect();
+
+    let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut variants: Vec<Variant> = vec![];
+    for (i, (graph, colours)) in graphs.iter().enumerate() {
+        let bucket = buckets.entry(signature(graph, colours)).or_default();
+        // an undecided test falls back to the shared signature and marks the variant as approximate
+        let existing = bucket.iter().copied().find_map(|v| {
+            let representative = variants[v].executions[0];
+            match is_isomorphic(graph, colours, &graphs[representative].0, &graphs[representative].1) {
+                Some(true) => Some((v, false)),
+                Some(false) => None,
+                None => Some((v, true))
+            }
+        });
+        match existing {
+            Some((v, approximate)) => {
+                variants[v].executions.push(i);
+                variants[v].approximate |= approximate;
+            },
+            None => {
+                bucket.push(variants.len());
+                variants.push(Variant { executions: vec![i], events: executions[i].events.len(), objects: executions[i].objects.len(), approximate: false });
+            }
+        }
+    }
+
+    variants.sort_by(|a, b| b.executions.len().cmp(&a.executions.len()).then(a.executions[0].cmp(&b.executions[0])));
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::fixtures::{event, object, log, eid};
+
+    fn graph(labels: &[u64], edges: &[(usize, usize, u64)]) -> (ExecutionGraph, Vec<u64>) {
+        let graph = ExecutionGraph { labels: labels.to_vec(), edges: edges.to_vec() };
+        let colours = wl_colours(&graph);
+        (graph, colours)
+    }
+
+    #[test]
+    fn isomorphism_ignores_event_order() {
+        let (g1, c1) = graph(&[1, 2, 3], &[(0, 1, 7), (1, 2, 7)]);
+        let (g2, c2) = graph(&[3, 1, 2], &[(1, 2, 7), (2, 0, 7)]);
+        let (g3, c3) = graph(&[1, 2, 3], &[(0, 1, 7), (0, 2, 7)]);
+        assert_eq!(is_isomorphic(&g1, &c1, &g2, &c2), Some(true));
+        assert_eq!(is_isomorphic(&g1, &c1, &g3, &c3), Some(false));
+    }
+
+    #[test]
+    fn large_graphs_are_undecided() {
+        let labels = vec![1; MAX_EXACT_ISOMORPHISM + 1];
+        let (g1, c1) = graph(&labels, &[]);
+        let (g2, c2) = graph(&labels, &[]);
+        assert_eq!(is_isomorphic(&g1, &c1, &g2, &c2), None);
+    }
+
+    #[test]
+    fn step_budget_stops_the_search() {
+        // isolated events followed by two triangles or one hexagon: every event has the same WL
+        // colour as its counterpart and the cycles only fail after all isolated events are mapped
+        let isolated = 20;
+        let cycles = |sizes: &[usize]| {
+            let mut edges: Vec<(usize, usize, u64)> = vec![];
+            let mut offset = isolated;
+            for size in sizes {
+                (0..*size).for_each(|i| edges.push((offset + i, offset + (i + 1) % size, 7)));
+                offset += size;
+            }
+            graph(&vec![1; offset], &edges)
+        };
+        let (g1, c1) = cycles(&[3, 3]);
+        let (g2, c2) = cycles(&[6]);
+        assert_eq!(signature(&g1, &c1), signature(&g2, &c2));
+        assert_eq!(is_isomorphic(&g1, &c1, &g2, &c2), None);
+    }
+
+    fn orders() -> Ocel {
+        log(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1", "i1"], json!({})),
+                 event("e2", "pay", "2022-01-01T11:00:00+00:00", &["o1"], json!({})),
+                 event("e3", "place", "2022-01-02T10:00:00+00:00", &["o2", "i2"], json!({})),
+                 event("e4", "pay", "2022-01-02T11:00:00+00:00", &["o2"], json!({})),
+                 event("e5", "place", "2022-01-03T10:00:00+00:00", &["o3"], json!({}))],
+            vec![object("o1", "order", json!({})), object("i1", "item", json!({})),
+                 object("o2", "order", json!({})), object("i2", "item", json!({})),
+                 object("o3", "order", json!({}))])
+    }
+
+    #[test]
+    fn variants_group_isomorphic_executions() {
+        let log = orders();
+        let executions = executions_by_shared_events(&log);
+        assert_eq!(executions.len(), 3);
+        assert_eq!(executions[0].events, vec![eid(&log, "e1"), eid(&log, "e2")]);
+
+        let variants = execution_variants(&log, &executions);
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].executions, vec![0, 1]);
+        assert_eq!((variants[0].events, variants[0].objects, variants[0].approximate), (2, 2, false));
+        assert_eq!(variants[1].executions, vec![2]);
+    }
+
+    #[test]
+    fn dangling_events_are_skipped() {
+        let mut log = orders();
+        log.events.remove(&eid(&log, "e2"));
+        let executions = executions_by_shared_events(&log);
+        assert_eq!(executions[0].events, vec![eid(&log, "e1")]);
+
+        // o1 still references its pay event, without it the execution looks like the one of o3
+        let variants = execution_variants(&log, &executions);
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].executions, vec![0, 2]);
+    }
+}
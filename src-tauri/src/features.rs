@@ -0,0 +1,381 @@
+use std::collections::HashSet;
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocdg::Relations;
+use pmrs::algo::transformation::ocel::features::object_point::ObjectPoint;
+use pmrs::algo::transformation::ocel::features::event_group::EventGroup;
+use pmrs::algo::transformation::ocel::features::operator::Operator;
+use serde_json::{Value, json};
+use strum::IntoEnumIterator;
+use itertools::Itertools;
+use polars::prelude::{DataFrame, Series, NamedFrom};
+
+
+// object point features that do not take any parameters
+pub const OBJECT_POINT_SIMPLE: [&str; 9] = ["UniqueNeighborCount", "ActivityExistence", "ActivityExistenceCount", "ObjectLifetime",
+                                            "ObjectEventInteractionOperator", "ObjectUnitSetRatio", "ObjectEventsDirectlyFollows",
+                                            "ObjectInputs", "ObjectOutputs"];
+// object point features that are computed once per given activity pair, object type or relation
+pub const OBJECT_POINT_PARAMETERISED: [&str; 3] = ["ObjectDirectRelationCount", "ObjectWaitTime", "ObjectTypeInteraction"];
+
+fn object_point(name: &str) -> Option<ObjectPoint> {
+    match name {
+        "UniqueNeighborCount" => Some(ObjectPoint::UniqueNeighborCount),
+        "ActivityExistence" => Some(ObjectPoint::ActivityExistence),
+        "ActivityExistenceCount" => Some(ObjectPoint::ActivityExistenceCount),
+        "ObjectLifetime" => Some(ObjectPoint::ObjectLifetime),
+        "ObjectEventInteractionOperator" => Some(ObjectPoint::ObjectEventInteractionOperator),
+        "ObjectUnitSetRatio" => Some(ObjectPoint::ObjectUnitSetRatio),
+        "ObjectEventsDirectlyFollows" => Some(ObjectPoint::ObjectEventsDirectlyFollows),
+        "ObjectInputs" => Some(ObjectPoint::ObjectInputs),
+        "ObjectOutputs" => Some(ObjectPoint::ObjectOutputs),
+        "ObjectDirectRelationCount" => Some(ObjectPoint::ObjectDirectRelationCount),
+        "ObjectWaitTime" => Some(ObjectPoint::ObjectWaitTime),
+        "ObjectTypeInteraction" => Some(ObjectPoint::ObjectTypeInteraction),
+        _ => None
+    }
+}
+
+// the user's choice of object point features and the values of their parameters
+#[derive(Debug, Default)]
+pub struct ObjectPointSelection<'a> {
+    pub features: Vec<&'a str>,
+    pub activity_pairs: Vec<(&'a str, &'a str)>,
+    pub object_types: Vec<String>,
+    pub relations: Vec<Relations>
+}
+
+// the parameters of the "Generate all Object Point Features" preset
+pub fn all_object_point_params(log: &Ocel) -> Result<Vec<(ObjectPoint, Option<Value>)>, String> {
+    let all_otypes_val: &Vec<Value> = match log.global_log.get("ocel:object-types") {
+        Some(Value::Array(otypes)) => otypes,
+        _ => {return Err("The OCEL does not list its object types (ocel:object-types)".to_string());}
+    };
+    let all_rels: Vec<Relations> = Relations::iter().collect();
+
+    let mut params: Vec<(ObjectPoint, Option<Value>)> = vec![];
+    OBJECT_POINT_SIMPLE.iter().for_each(|name| params.push((object_point(name).unwrap(), None)));
+
+    // all rels
+    all_rels.iter()
+            .for_each(|rel| {
+                params.push((ObjectPoint::ObjectDirectRelationCount, Some(json!({"relations": format!("{:?}", rel).as_str()}))));
+            });
+
+    // double act
+    log.activities.iter()
+                  .cartesian_product(&log.activities)
+                  .for_each(|(a1, a2)| {
+                    params.push((ObjectPoint::ObjectWaitTime, Some(json!({"activity_src": a1, "activity_tar": a2}))));
+                  });
+
+    // otypes
+    all_otypes_val.iter()
+                  .for_each(|ot| {
+                    params.push((ObjectPoint::ObjectTypeInteraction, Some(json!({"object_type": ot}))));
+                  });
+
+    // attribute operator features are not object point features, see attribute_operator_features
+
+    Ok(params)
+}
+
+pub fn selected_object_point_params(selection: &ObjectPointSelection) -> Result<Vec<(ObjectPoint, Option<Value>)>, String> {
+    let mut params: Vec<(ObjectPoint, Option<Value>)> = vec![];
+
+    for name in &selection.features {
+        match *name {
+            "ObjectDirectRelationCount" => {
+                selection.relations.iter().for_each(|rel| {
+                    params.push((ObjectPoint::ObjectDirectRelationCount, Some(json!({"relations": format!("{:?}", rel).as_str()}))));
+                });
+            },
+            "ObjectWaitTime" => {
+                if selection.activity_pairs.is_empty() {
+                    return Err("ObjectWaitTime requires at least one activity pair".to_string());
+                }
+                selection.activity_pairs.iter().for_each(|(a1, a2)| {
+                    params.push((ObjectPoint::ObjectWaitTime, Some(json!({"activity_src": a1, "activity_tar": a2}))));
+                });
+            },
+            "ObjectTypeInteraction" => {
+                selection.object_types.iter().for_each(|ot| {
+                    params.push((ObjectPoint::ObjectTypeInteraction, Some(json!({"object_type": ot}))));
+                });
+            },
+            simple => {
+                match object_point(simple) {
+                    Some(feature) => params.push((feature, None)),
+                    None => {return Err(format!("Unknown object point feature {}", simple));}
+                }
+            }
+        }
+    }
+
+    if params.is_empty() {
+        return Err("No object point features selected".to_string());
+    }
+
+    Ok(params)
+}
+
+// parses "src->tar;src2->tar2"
+pub fn parse_activity_pairs(input: &str) -> Result<Vec<(&str, &str)>, String> {
+    input.split(";")
+         .filter(|pair| !pair.is_empty())
+         .map(|pair| pair.split_once("->").map(|(a, b)| (a.trim(), b.trim())).ok_or(format!("Invalid activity pair {}", pair)))
+         .collect()
+}
+
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None
+    }
+}
+
+pub struct AttributeFeatures {
+    pub df: DataFrame,
+    // (attribute or operator, reason)
+    pub skipped: Vec<(String, String)>
+}
+
+// For every object, aggregate the values of the given event attributes over its events with every
+// operator, optionally per activity. Attributes with non-numeric values are skipped.
+pub fn attribute_operator_features(log: &Ocel, attributes: &[String], operators: &[String], per_activity: bool) -> AttributeFeatures {
+    let mut skipped: Vec<(String, String)> = vec![];
+    operators.iter()
+             .filter(|op| !Operator::iter().any(|known| known.to_string() == op.as_str()))
+             .for_each(|op| skipped.push((op.to_string(), "unknown operator".to_string())));
+    // aggregate with the pmrs operators so the semantics match the other operator features
+    let operators: Vec<Operator> = Operator::iter().filter(|op| operators.contains(&op.to_string())).collect();
+
+    let mut numeric: Vec<&String> = vec![];
+    for attr in attributes {
+        let values: Vec<&Value> = log.events.values().filter_map(|ev| ev.vmap.get(attr)).collect();
+        if values.is_empty() {
+            skipped.push((attr.to_string(), "not an event attribute".to_string()));
+        } else if let Some(bad) = values.iter().find(|v| numeric_value(v).is_none()) {
+            skipped.push((attr.to_string(), format!("non-numeric value {}", bad)));
+        } else {
+            numeric.push(attr);
+        }
+    }
+
+    let activities: Vec<Option<&str>> = if per_activity {
+        let present: HashSet<&str> = log.events.values().map(|ev| ev.activity.as_str()).collect();
+        log.activities.iter().filter(|a| present.contains(a.as_str())).map(|a| Some(a.as_str())).collect()
+    } else {
+        vec![None]
+    };
+
+    let object_count = log.objects.len();
+    let mut columns: Vec<Series> = vec![Series::new("Object", (0..object_count).map(|i| log.object_map.get_by_right(&i).unwrap().as_str()).collect::<Vec<&str>>())];
+
+    for attr in &numeric {
+        for act in &activities {
+            // values of the attribute for every object
+            let values: Vec<Vec<f64>> = (0..object_count).map(|oid| {
+                log.objects[&oid].events.iter()
+                                        .filter_map(|eid| log.events.get(eid))
+                                        .filter(|ev| act.map_or(true, |a| ev.activity == a))
+                                        .filter_map(|ev| ev.vmap.get(*attr).and_then(numeric_value))
+                                        .collect()
+            }).collect();
+
+            if act.is_some() && values.iter().all(|v| v.is_empty()) {
+                continue;
+            }
+
+            for op in &operators {
+                let name = match act {
+                    Some(a) => format!("{}:{}:{}", attr, a, op),
+                    None => format!("{}:{}", attr, op)
+                };
+                let column: Vec<Option<f64>> = values.iter().map(|v| if v.is_empty() { None } else { Some(op.execute(v)) }).collect();
+                columns.push(Series::new(name.as_str(), column));
+            }
+        }
+    }
+
+    AttributeFeatures { df: DataFrame::new(columns).expect("Data Table Creation went wrong"), skipped }
+}
+
+pub const EVENT_GROUP_FEATURES: [&str; 5] = ["ActivityCounts", "ActivityObjectTypeOperator", "ActivityAttrOperator",
+                                             "ActivityWaitTimeOperator", "ActivityActiveTimeOperator"];
+
+// Build the event group parameters for the chosen features, operators and activities in the
+// same nesting as the "Generate all Event Group Features" preset.
+pub fn selected_event_group_params(features: &[&str], operators: &[String], activities: &[&str]) -> Result<Vec<(EventGroup, Option<Value>)>, String> {
+    let mut params: Vec<(EventGroup, Option<Value>)> = vec![];
+    let chosen: HashSet<&str> = features.iter().copied().collect();
+    if let Some(unknown) = chosen.iter().find(|f| !EVENT_GROUP_FEATURES.contains(f)) {
+        return Err(format!("Unknown event group feature {}", unknown));
+    }
+
+    if chosen.contains("ActivityCounts") {
+        params.push((EventGroup::ActivityCounts, None));
+    }
+
+    let needs_operator = chosen.iter().any(|f| *f != "ActivityCounts");
+    if needs_operator && operators.is_empty() {
+        return Err("The selected event group features require at least one operator".to_string());
+    }
+
+    operators.iter()
+             .for_each(|op| {
+                 if chosen.contains("ActivityObjectTypeOperator") {
+                     params.push((EventGroup::ActivityObjectTypeOperator, Some(json!({"operator": op}))));
+                 }
+                 activities.iter()
+                           .for_each(|act| {
+                               if chosen.contains("ActivityAttrOperator") {
+                                   params.push((EventGroup::ActivityAttrOperator, Some(json!({"operator": op, "activity": act}))));
+                               }
+                               if chosen.contains("ActivityWaitTimeOperator") {
+                                   params.push((EventGroup::ActivityWaitTimeOperator, Some(json!({"operator": op, "activity": act}))));
+                               }
+                               if chosen.contains("ActivityActiveTimeOperator") {
+                                   params.push((EventGroup::ActivityActiveTimeOperator, Some(json!({"operator": op, "activity": act}))));
+                               }
+                           });
+             });
+
+    if params.is_empty() {
+        return Err("No event group features selected".to_string());
+    }
+
+    Ok(params)
+}
+
+// number of columns every chosen event group feature generates, used for dry runs. Activity
+// counts have a column per activity of the log, the object type operator a column per activity
+// and object type and the attribute operator a column per attribute occurring on the activity,
+// all for every operator. Wait and active times have a single column per activity and operator.
+pub fn count_event_group_columns(log: &Ocel, features: &[&str], operators: &[String], activities: &[&str]) -> Vec<(&'static str, usize)> {
+    let object_types: HashSet<&str> = log.objects.values().map(|obj| obj.obj_type.as_str()).collect();
+    let attributes = |act: &str| -> usize {
+        log.events.values()
+                  .filter(|ev| ev.activity == act)
+                  .flat_map(|ev| ev.vmap.keys())
+                  .collect::<HashSet<&String>>()
+                  .len()
+    };
+    EVENT_GROUP_FEATURES.iter()
+                        .filter(|f| features.contains(f))
+                        .map(|f| {
+                            let count = match *f {
+                                "ActivityCounts" => log.activities.len(),
+                                "ActivityObjectTypeOperator" => operators.len() * log.activities.len() * object_types.len(),
+                                "ActivityAttrOperator" => operators.len() * activities.iter().map(|act| attributes(act)).sum::<usize>(),
+                                _ => operators.len() * activities.len()
+                            };
+                            (*f, count)
+                        })
+                        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{event, object, log, oid};
+
+    fn orders() -> Ocel {
+        log(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1", "i1"], json!({"cost": 10, "status": "new"})),
+                 event("e2", "pick", "2022-01-01T11:00:00+00:00", &["i1"], json!({"cost": "2.5"})),
+                 event("e3", "pay", "2022-01-01T12:00:00+00:00", &["o1"], json!({"cost": 4}))],
+            vec![object("o1", "order", json!({})), object("i1", "item", json!({}))])
+    }
+
+    #[test]
+    fn all_object_point_params_need_the_object_types() {
+        let mut log = orders();
+        // 9 simple features, every relation, every activity pair and every object type
+        let expected = OBJECT_POINT_SIMPLE.len() + Relations::iter().count() + 3 * 3 + 2;
+        assert_eq!(all_object_point_params(&log).unwrap().len(), expected);
+
+        log.global_log.remove("ocel:object-types");
+        assert!(all_object_point_params(&log).is_err());
+    }
+
+    #[test]
+    fn selected_object_point_params_expand_their_parameters() {
+        let selection = ObjectPointSelection {
+            features: vec!["ObjectLifetime", "ObjectWaitTime", "ObjectTypeInteraction"],
+            activity_pairs: parse_activity_pairs("place->pay; pick -> pay").unwrap(),
+            object_types: vec!["order".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(selection.activity_pairs, vec![("place", "pay"), ("pick", "pay")]);
+
+        let params = selected_object_point_params(&selection).unwrap();
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[1].1, Some(json!({"activity_src": "place", "activity_tar": "pay"})));
+        assert_eq!(params[3].1, Some(json!({"object_type": "order"})));
+    }
+
+    #[test]
+    fn invalid_object_point_selections() {
+        assert!(parse_activity_pairs("place;pay").is_err());
+        assert!(selected_object_point_params(&ObjectPointSelection { features: vec!["ObjectWaitTime"], ..Default::default() }).is_err());
+        assert!(selected_object_point_params(&ObjectPointSelection { features: vec!["Unknown"], ..Default::default() }).is_err());
+        // a parameterised feature without any parameters generates nothing
+        assert!(selected_object_point_params(&ObjectPointSelection { features: vec!["ObjectTypeInteraction"], ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn attribute_operators_skip_unusable_inputs() {
+        let log = orders();
+        let mut operators: Vec<String> = Operator::iter().map(|op| op.to_string()).collect();
+        operators.push("Nope".to_string());
+        let attributes = vec!["cost".to_string(), "status".to_string(), "weight".to_string()];
+
+        let features = attribute_operator_features(&log, &attributes, &operators, false);
+        let skipped: Vec<&str> = features.skipped.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(skipped, vec!["Nope", "status", "weight"]);
+        assert_eq!(features.df.width(), 1 + Operator::iter().count());
+        assert_eq!(features.df.height(), 2);
+    }
+
+    #[test]
+    fn attribute_operators_per_activity() {
+        let log = orders();
+        let op = Operator::iter().next().unwrap().to_string();
+        let features = attribute_operator_features(&log, &["cost".to_string()], &[op.to_string()], true);
+        assert_eq!(features.df.width(), 1 + 3);
+
+        // the item has no pay event and the order no pick event
+        let column = |act: &str| features.df.column(&format!("cost:{}:{}", act, op)).unwrap().f64().unwrap().clone();
+        assert!(column("pay").get(oid(&log, "i1")).is_none());
+        assert!(column("pick").get(oid(&log, "o1")).is_none());
+        assert!(column("pick").get(oid(&log, "i1")).is_some());
+    }
+
+    #[test]
+    fn event_group_selection() {
+        let operators = vec!["Mean".to_string(), "Max".to_string()];
+        let params = selected_event_group_params(&EVENT_GROUP_FEATURES, &operators, &["place", "pick"]).unwrap();
+        // activity counts once, the object type operator per operator, the rest per operator and activity
+        assert_eq!(params.len(), 1 + 2 * (1 + 2 * 3));
+        assert_eq!(params[2].1, Some(json!({"operator": "Mean", "activity": "place"})));
+
+        assert_eq!(selected_event_group_params(&["ActivityCounts"], &[], &[]).unwrap().len(), 1);
+        assert!(selected_event_group_params(&["ActivityWaitTimeOperator"], &[], &["place"]).is_err());
+        assert!(selected_event_group_params(&["Unknown"], &operators, &[]).is_err());
+        assert!(selected_event_group_params(&[], &operators, &[]).is_err());
+    }
+
+    #[test]
+    fn dry_run_counts_generated_columns() {
+        let log = orders();
+        let operators = vec!["Mean".to_string(), "Max".to_string()];
+        let counts = count_event_group_columns(&log, &EVENT_GROUP_FEATURES, &operators, &["place", "pick"]);
+        assert_eq!(counts, vec![("ActivityCounts", 3),
+                                ("ActivityObjectTypeOperator", 2 * 3 * 2),
+                                ("ActivityAttrOperator", 2 * (2 + 1)),
+                                ("ActivityWaitTimeOperator", 2 * 2),
+                                ("ActivityActiveTimeOperator", 2 * 2)]);
+        assert_eq!(count_event_group_columns(&log, &["ActivityCounts"], &[], &[]), vec![("ActivityCounts", 3)]);
+    }
+}
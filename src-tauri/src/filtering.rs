@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc, NaiveDateTime, NaiveDate, TimeZone};
+use pmrs::objects::ocel::Ocel;
+use serde_json::Value;
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Neq,
+    Lt,
+    Leq,
+    Gt,
+    Geq
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributePredicate {
+    pub attribute: String,
+    pub comparison: Comparison,
+    pub value: Value
+}
+
+impl AttributePredicate {
+    // parses predicates of the form "attribute>=value", numbers are compared numerically
+    pub fn parse(predicate: &str) -> Result<AttributePredicate, String> {
+        for (symbol, comparison) in [(">=", Comparison::Geq), ("<=", Comparison::Leq), ("!=", Comparison::Neq),
+                                     ("==", Comparison::Eq), (">", Comparison::Gt), ("<", Comparison::Lt)] {
+            if let Some((attribute, value)) = predicate.split_once(symbol) {
+                let attribute = attribute.trim();
+                if attribute.is_empty() {
+                    return Err(format!("Missing attribute in predicate {}", predicate));
+                }
+                let value = value.trim();
+                let value = match value.parse::<f64>() {
+                    Ok(number) => Value::from(number),
+                    Err(_) => Value::String(value.to_string())
+                };
+                return Ok(AttributePredicate { attribute: attribute.to_string(), comparison, value });
+            }
+        }
+        Err(format!("Invalid attribute predicate {}", predicate))
+    }
+
+    pub fn holds(&self, vmap: &HashMap<String, Value>) -> bool {
+        let actual = match vmap.get(&self.attribute) {
+            Some(v) => v,
+            None => {return false;}
+        };
+
+        let ordering = match (actual.as_f64(), self.value.as_f64()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => {
+                let a = match actual {Value::String(s) => s.to_string(), v => v.to_string()};
+                let b = match &self.value {Value::String(s) => s.to_string(), v => v.to_string()};
+                Some(a.cmp(&b))
+            }
+        };
+
+        match ordering {
+            Some(ord) => match self.comparison {
+                Comparison::Eq => ord.is_eq(),
+                Comparison::Neq => ord.is_ne(),
+                Comparison::Lt => ord.is_lt(),
+                Comparison::Leq => ord.is_le(),
+                Comparison::Gt => ord.is_gt(),
+                Comparison::Geq => ord.is_ge(),
+            },
+            None => false
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OcelFilter<'a> {
+    pub include_activities: Option<HashSet<&'a str>>,
+    pub exclude_activities: Option<HashSet<&'a str>>,
+    pub include_object_types: Option<HashSet<&'a str>>,
+    pub exclude_object_types: Option<HashSet<&'a str>>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub predicates: Vec<AttributePredicate>
+}
+
+pub fn parse_timestamp(timestamp: &str) -> Result<DateTime<Utc>, String> {
+    let timestamp = timestamp.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(timestamp) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S") {
+        return Ok(Utc.from_utc_datetime(&dt));
+    }
+    if let Ok(d) = NaiveDate::parse_from_str(timestamp, "%Y-%m-%d") {
+        return Ok(Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).expect("midnight is always valid")));
+    }
+    Err(format!("Could not parse timestamp {}", timestamp))
+}
+
+pub type TimeBounds = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+// the optional string:StartTime and string:EndTime parameters of a plugin, empty bounds are open
+pub fn parse_time_bounds(params: &Value) -> Result<TimeBounds, String> {
+    let bound = |key: &str| match params[key].as_str() {
+        Some(time) if !time.is_empty() => parse_timestamp(time).map(Some),
+        _ => Ok(None)
+    };
+    let (start, end) = (bound("string:StartTime")?, bound("string:EndTime")?);
+    if let (Some(s), Some(e)) = (start, end) {
+        if s > e {
+            return Err(format!("StartTime {} lies after EndTime {}", s, e));
+        }
+    }
+    Ok((start, end))
+}
+
+// Create a new log containing only the given events and objects. Events lose all
+// references to objects that are not kept and are dropped if they have none left
+// (events that never had objects are kept). Objects without any remaining events
+// are pruned and both maps are re-indexed.
+pub fn extract_sublog(log: &Ocel, events: &HashSet<usize>, objects: &HashSet<usize>) -> Ocel {
+    let mut kept_events: Vec<usize> = events.iter()
+                                            .filter(|eid| log.events.get(eid).map_or(false, |ev| ev.omap.is_empty() || ev.omap.iter().any(|oid| objects.contains(oid))))
+                                            .copied()
+                                            .collect();
+    kept_events.sort_unstable();
+
+    let mut kept_objects: Vec<usize> = kept_events.iter()
+                                                  .flat_map(|eid| log.events[eid].omap.iter().filter(|oid| objects.contains(oid)))
+                                                  .copied()
+                                                  .collect::<HashSet<usize>>()
+                                                  .into_iter()
+                                                  .collect();
+    kept_objects.sort_unstable();
+
+    let event_index: HashMap<usize, usize> = kept_events.iter().enumerate().map(|(new, old)| (*old, new)).collect();
+    let object_index: HashMap<usize, usize> = kept_objects.iter().enumerate().map(|(new, old)| (*old, new)).collect();
+
+    let mut sublog = log.clone();
+    sublog.events.clear();
+    sublog.objects.clear();
+    sublog.event_map.clear();
+    sublog.object_map.clear();
+
+    for (new, old) in kept_events.iter().enumerate() {
+        let mut ev = log.events[old].clone();
+        ev.omap = ev.omap.iter().filter_map(|oid| object_index.get(oid)).copied().collect();
+        sublog.events.insert(new, ev);
+        sublog.event_map.insert(log.event_map.get_by_right(old).expect("event must be mapped").to_string(), new);
+    }
+
+    for (new, old) in kept_objects.iter().enumerate() {
+        let mut obj = log.objects[old].clone();
+        obj.events = obj.events.iter().filter_map(|eid| event_index.get(eid)).copied().collect();
+        sublog.objects.insert(new, obj);
+        sublog.object_map.insert(log.object_map.get_by_right(old).expect("object must be mapped").to_string(), new);
+    }
+
+    let present_activities: HashSet<&str> = sublog.events.values().map(|ev| ev.activity.as_str()).collect();
+    sublog.activities = log.activities.iter().filter(|act| present_activities.contains(act.as_str())).cloned().collect();
+
+    let present_otypes: HashSet<&str> = sublog.objects.values().map(|obj| obj.obj_type.as_str()).collect();
+    if let Some(Value::Array(otypes)) = sublog.global_log.get_mut("ocel:object-types") {
+        otypes.retain(|ot| ot.as_str().map_or(false, |ot| present_otypes.contains(ot)));
+    }
+
+    sublog
+}
+
+pub fn filter_ocel(log: &Ocel, filter: &OcelFilter) -> Ocel {
+    let objects: HashSet<usize> = log.objects.iter()
+                                             .filter(|(_, obj)| {
+                                                 filter.include_object_types.as_ref().map_or(true, |inc| inc.contains(obj.obj_type.as_str())) &&
+                                                 !filter.exclude_object_types.as_ref().map_or(false, |exc| exc.contains(obj.obj_type.as_str()))
+                                             })
+                                             .map(|(oid, _)| *oid)
+                                             .collect();
+
+    let events: HashSet<usize> = log.events.iter()
+                                           .filter(|(_, ev)| {
+                                               filter.include_activities.as_ref().map_or(true, |inc| inc.contains(ev.activity.as_str())) &&
+                                               !filter.exclude_activities.as_ref().map_or(false, |exc| exc.contains(ev.activity.as_str())) &&
+                                               filter.start.map_or(true, |start| ev.timestamp >= start) &&
+                                               filter.end.map_or(true, |end| ev.timestamp <= end) &&
+                                               filter.predicates.iter().all(|pred| pred.holds(&ev.vmap))
+                                           })
+                                           .map(|(eid, _)| *eid)
+                                           .collect();
+
+    extract_sublog(log, &events, &objects)
+}
+
+// Sub-log around table ids that are either object or event ids: objects keep all of their
+// events, events keep all of their objects.
+pub fn sublog_from_ids(log: &Ocel, ids: &[&str]) -> Ocel {
+    let mut events: HashSet<usize> = HashSet::new();
+    let mut objects: HashSet<usize> = HashSet::new();
+    for id in ids {
+        if let Some(oid) = log.object_map.get_by_left(*id) {
+            objects.insert(*oid);
+            events.extend(log.objects[oid].events.iter().copied());
+        } else if let Some(eid) = log.event_map.get_by_left(*id) {
+            events.insert(*eid);
+            objects.extend(log.events[eid].omap.iter().copied());
+        }
+    }
+    extract_sublog(log, &events, &objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::fixtures::{event, object, log, eid, oid};
+
+    fn vmap(value: Value) -> HashMap<String, Value> {
+        value.as_object().unwrap().iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn parse_predicates() {
+        let pred = AttributePredicate::parse("cost >= 10").unwrap();
+        assert_eq!(pred.attribute, "cost");
+        assert_eq!(pred.comparison, Comparison::Geq);
+        assert_eq!(pred.value.as_f64(), Some(10.0));
+
+        let pred = AttributePredicate::parse("status==open").unwrap();
+        assert_eq!(pred.comparison, Comparison::Eq);
+        assert_eq!(pred.value, json!("open"));
+
+        assert_eq!(AttributePredicate::parse("cost<5").unwrap().comparison, Comparison::Lt);
+        assert_eq!(AttributePredicate::parse("cost!=5").unwrap().comparison, Comparison::Neq);
+        assert!(AttributePredicate::parse(">= 10").is_err());
+        assert!(AttributePredicate::parse("cost 10").is_err());
+    }
+
+    #[test]
+    fn predicates_compare_numbers_and_strings() {
+        let at_least_ten = AttributePredicate::parse("cost>=10").unwrap();
+        assert!(at_least_ten.holds(&vmap(json!({"cost": 12}))));
+        assert!(at_least_ten.holds(&vmap(json!({"cost": 10.0}))));
+        assert!(!at_least_ten.holds(&vmap(json!({"cost": 9}))));
+        assert!(!at_least_ten.holds(&vmap(json!({"price": 12}))));
+
+        let not_closed = AttributePredicate::parse("status!=closed").unwrap();
+        assert!(not_closed.holds(&vmap(json!({"status": "open"}))));
+        assert!(!not_closed.holds(&vmap(json!({"status": "closed"}))));
+    }
+
+    fn utc(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&NaiveDate::from_ymd_opt(2022, 3, day).unwrap().and_hms_opt(hour, minute, 0).unwrap())
+    }
+
+    #[test]
+    fn parse_timestamps() {
+        assert_eq!(parse_timestamp("2022-03-01T12:30:00+02:00"), Ok(utc(1, 10, 30)));
+        assert_eq!(parse_timestamp("2022-03-01 10:30:00"), Ok(utc(1, 10, 30)));
+        assert_eq!(parse_timestamp(" 2022-03-01 "), Ok(utc(1, 0, 0)));
+        assert!(parse_timestamp("01.03.2022").is_err());
+    }
+
+    #[test]
+    fn parse_time_bounds_leaves_empty_bounds_open() {
+        assert_eq!(parse_time_bounds(&json!({"string:StartTime": "2022-03-01", "string:EndTime": ""})), Ok((Some(utc(1, 0, 0)), None)));
+        assert_eq!(parse_time_bounds(&json!({"string:EndTime": "2022-03-01 10:30:00"})), Ok((None, Some(utc(1, 10, 30)))));
+        assert_eq!(parse_time_bounds(&json!({"string:StartTime": "2022-03-01", "string:EndTime": "2022-03-01"})), Ok((Some(utc(1, 0, 0)), Some(utc(1, 0, 0)))));
+        assert!(parse_time_bounds(&json!({"string:StartTime": "yesterday"})).is_err());
+        assert!(parse_time_bounds(&json!({"string:StartTime": "2022-03-01 10:30:00", "string:EndTime": "2022-03-01"})).is_err());
+    }
+
+    fn orders() -> Ocel {
+        log(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1", "i1"], json!({"cost": 10})),
+                 event("e2", "pick", "2022-01-02T10:00:00+00:00", &["i1"], json!({})),
+                 event("e3", "audit", "2022-01-03T10:00:00+00:00", &[], json!({"cost": 3}))],
+            vec![object("o1", "order", json!({})),
+                 object("i1", "item", json!({}))])
+    }
+
+    #[test]
+    fn sublog_drops_references_to_removed_objects() {
+        let log = orders();
+        let events: HashSet<usize> = log.events.keys().copied().collect();
+        let sublog = extract_sublog(&log, &events, &HashSet::from([oid(&log, "o1")]));
+
+        // e2 loses its only object, e3 never had one
+        let mut kept: Vec<&String> = sublog.event_map.left_values().collect();
+        kept.sort();
+        assert_eq!(kept, vec!["e1", "e3"]);
+        assert_eq!(sublog.object_map.left_values().collect::<Vec<&String>>(), vec!["o1"]);
+
+        let e1 = &sublog.events[&eid(&sublog, "e1")];
+        assert_eq!(e1.omap.iter().copied().collect::<Vec<usize>>(), vec![oid(&sublog, "o1")]);
+        let o1 = &sublog.objects[&oid(&sublog, "o1")];
+        assert_eq!(o1.events.iter().copied().collect::<Vec<usize>>(), vec![eid(&sublog, "e1")]);
+        assert_eq!(sublog.global_log["ocel:object-types"], json!(["order"]));
+    }
+
+    #[test]
+    fn filter_keeps_objectless_events_that_match() {
+        let log = orders();
+        let filter = OcelFilter { exclude_activities: Some(HashSet::from(["pick"])),
+                                  predicates: vec![AttributePredicate::parse("cost>=3").unwrap()],
+                                  ..Default::default() };
+        let filtered = filter_ocel(&log, &filter);
+        let mut kept: Vec<&String> = filtered.event_map.left_values().collect();
+        kept.sort();
+        assert_eq!(kept, vec!["e1", "e3"]);
+        assert_eq!(filtered.activities.len(), 2);
+    }
+}
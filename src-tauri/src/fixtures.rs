@@ -0,0 +1,55 @@
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::{Value, Map, json};
+
+// Small logs for the unit tests. They are written as JSON-OCEL and read back with the importer
+// of the app, so the ids are assigned exactly like for an imported log.
+
+static LOG_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+pub fn event(id: &str, activity: &str, timestamp: &str, omap: &[&str], vmap: Value) -> (String, Value) {
+    (id.to_string(), json!({"ocel:activity": activity, "ocel:timestamp": timestamp, "ocel:omap": omap, "ocel:vmap": vmap}))
+}
+
+pub fn object(id: &str, object_type: &str, ovmap: Value) -> (String, Value) {
+    (id.to_string(), json!({"ocel:type": object_type, "ocel:ovmap": ovmap}))
+}
+
+pub fn log(events: Vec<(String, Value)>, objects: Vec<(String, Value)>) -> Ocel {
+    log_declaring(events, objects, &[])
+}
+
+// attribute names that are declared in the global log on top of the ones that occur
+pub fn log_declaring(events: Vec<(String, Value)>, objects: Vec<(String, Value)>, declared: &[&str]) -> Ocel {
+    let keys = |entries: &[(String, Value)], map: &str| -> BTreeSet<String> {
+        entries.iter().filter_map(|(_, entry)| entry[map].as_object()).flat_map(|vmap| vmap.keys().cloned()).collect()
+    };
+    let mut attribute_names = keys(&events, "ocel:vmap");
+    attribute_names.extend(keys(&objects, "ocel:ovmap"));
+    attribute_names.extend(declared.iter().map(|name| name.to_string()));
+    let object_types: BTreeSet<&str> = objects.iter().filter_map(|(_, obj)| obj["ocel:type"].as_str()).collect();
+
+    let content = json!({
+        "ocel:global-event": {"ocel:activity": "__INVALID__"},
+        "ocel:global-object": {"ocel:type": "__INVALID__"},
+        "ocel:global-log": {"ocel:attribute-names": attribute_names, "ocel:object-types": object_types, "ocel:version": "1.0", "ocel:ordering": "timestamp"},
+        "ocel:events": Map::from_iter(events.iter().cloned()),
+        "ocel:objects": Map::from_iter(objects.iter().cloned())
+    });
+
+    let path = std::env::temp_dir().join(format!("process-tool-test-{}-{}.jsonocel", std::process::id(), LOG_COUNTER.fetch_add(1, Ordering::SeqCst)));
+    std::fs::write(&path, content.to_string()).expect("temporary log can be written");
+    let ocel = import_ocel(path.to_str().expect("temporary path is valid unicode")).expect("test log is valid JSON-OCEL");
+    let _ = std::fs::remove_file(&path);
+    ocel
+}
+
+pub fn eid(log: &Ocel, id: &str) -> usize {
+    *log.event_map.get_by_left(id).expect("event is in the log")
+}
+
+pub fn oid(log: &Ocel, id: &str) -> usize {
+    *log.object_map.get_by_left(id).expect("object is in the log")
+}
@@ -0,0 +1,227 @@
+use strum::EnumString;
+use crate::numeric::{least_squares, mean, variance};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum Method {
+    SimpleExponentialSmoothing,
+    Holt,
+    HoltWinters,
+    Autoregression
+}
+
+// smoothing parameters of 0 are chosen by a grid search on the one-step errors
+#[derive(Debug, Clone, Copy)]
+pub struct ForecastConfig {
+    pub method: Method,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub season_length: usize,
+    pub lags: usize
+}
+
+#[derive(Debug, Clone)]
+pub struct Forecast {
+    pub values: Vec<f64>,
+    // standard deviation of the one-step in-sample errors
+    pub residual_std: f64,
+    // the parameters that were actually used
+    pub parameters: Vec<(String, f64)>
+}
+
+// additive Holt-Winters, without trend / season it reduces to Holt and simple smoothing.
+// Returns the one-step errors and the forecast.
+fn smoothing(series: &[f64], alpha: f64, beta: Option<f64>, gamma: Option<(f64, usize)>, horizon: usize) -> (Vec<f64>, Vec<f64>) {
+    let m = gamma.map_or(1, |(_, m)| m);
+    let mut level = match gamma {
+        Some(_) => mean(&series[..m]),
+        None => series[0]
+    };
+    let mut trend = match (beta, gamma) {
+        (Some(_), Some(_)) if series.len() >= 2 * m => (mean(&series[m..2 * m]) - mean(&series[..m])) / m as f64,
+        (Some(_), _) if series.len() >= 2 => series[1] - series[0],
+        _ => 0.0
+    };
+    let mut season: Vec<f64> = match gamma {
+        Some(_) => series[..m].iter().map(|v| v - level).collect(),
+        None => vec![0.0]
+    };
+
+    let start = if gamma.is_some() { m } else { 1 };
+    let mut errors: Vec<f64> = vec![];
+    for (t, value) in series.iter().enumerate().skip(start) {
+        let s = season[t % m];
+        errors.push(value - (level + trend + s));
+
+        let previous = level;
+        level = alpha * (value - s) + (1.0 - alpha) * (level + trend);
+        if let Some(beta) = beta {
+            trend = beta * (level - previous) + (1.0 - beta) * trend;
+        }
+        if let Some((gamma, _)) = gamma {
+            season[t % m] = gamma * (value - level) + (1.0 - gamma) * s;
+        }
+    }
+
+    let n = series.len();
+    let forecast = (1..=horizon).map(|h| level + h as f64 * trend + season[(n + h - 1) % m]).collect();
+    (errors, forecast)
+}
+
+fn sse(errors: &[f64]) -> f64 {
+    errors.iter().map(|e| e * e).sum()
+}
+
+fn grid(fixed: f64) -> Vec<f64> {
+    if fixed > 0.0 { vec![fixed.min(1.0)] } else { (1..10).map(|i| i as f64 / 10.0).collect() }
+}
+
+fn exponential_smoothing(series: &[f64], config: &ForecastConfig, horizon: usize) -> Result<Forecast, String> {
+    let seasonal = config.method == Method::HoltWinters;
+    let trended = config.method != Method::SimpleExponentialSmoothing;
+    if seasonal && (config.season_length < 2 || series.len() < 2 * config.season_length) {
+        return Err("Holt-Winters needs a season length of at least 2 and two full seasons of data".to_string());
+    }
+    if series.len() < 2 {
+        return Err("The series needs at least two values".to_string());
+    }
+
+    let betas = if trended { grid(config.beta) } else { vec![0.0] };
+    let gammas = if seasonal { grid(config.gamma) } else { vec![0.0] };
+    let mut best: Option<(f64, f64, f64, f64)> = None;
+    for alpha in grid(config.alpha) {
+        for beta in &betas {
+            for gamma in &gammas {
+                let (errors, _) = smoothing(series, alpha, if trended { Some(*beta) } else { None }, if seasonal { Some((*gamma, config.season_length)) } else { None }, 0);
+                let score = sse(&errors);
+                if best.map_or(true, |(s, _, _, _)| score < s) {
+                    best = Some((score, alpha, *beta, *gamma));
+                }
+            }
+        }
+    }
+
+    let (_, alpha, beta, gamma) = best.expect("the grid is never empty");
+    let (errors, values) = smoothing(series, alpha, if trended { Some(beta) } else { None }, if seasonal { Some((gamma, config.season_length)) } else { None }, horizon);
+    let mut parameters = vec![("alpha".to_string(), alpha)];
+    if trended {
+        parameters.push(("beta".to_string(), beta));
+    }
+    if seasonal {
+        parameters.push(("gamma".to_string(), gamma));
+    }
+    Ok(Forecast { values, residual_std: variance(&errors).sqrt(), parameters })
+}
+
+// AR(p) with intercept fitted by least squares, multi-step forecasts are recursive
+fn autoregression(series: &[f64], lags: usize, horizon: usize) -> Result<Forecast, String> {
+    let p = lags.max(1);
+    if series.len() < 2 * p + 2 {
+        return Err(format!("AR({}) needs at least {} values", p, 2 * p + 2));
+    }
+
+    let rows: Vec<Vec<f64>> = (p..series.len()).map(|t| {
+        let mut row = vec![1.0];
+        row.extend((1..=p).map(|lag| series[t - lag]));
+        row
+    }).collect();
+    let coefficients = least_squares(&rows, &series[p..]).ok_or("The autoregression could not be fitted (singular system)")?;
+    let predict = |history: &[f64]| -> f64 {
+        coefficients[0] + (1..=p).map(|lag| coefficients[lag] * history[history.len() - lag]).sum::<f64>()
+    };
+
+    let errors: Vec<f64> = (p..series.len()).map(|t| series[t] - predict(&series[..t])).collect();
+    let mut history = series.to_vec();
+    let values: Vec<f64> = (0..horizon).map(|_| {
+        let next = predict(&history);
+        history.push(next);
+        next
+    }).collect();
+
+    let mut parameters = vec![("intercept".to_string(), coefficients[0])];
+    parameters.extend((1..=p).map(|lag| (format!("lag {}", lag), coefficients[lag])));
+    Ok(Forecast { values, residual_std: variance(&errors).sqrt(), parameters })
+}
+
+pub fn forecast(series: &[f64], config: &ForecastConfig, horizon: usize) -> Result<Forecast, String> {
+    match config.method {
+        Method::Autoregression => autoregression(series, config.lags, horizon),
+        _ => exponential_smoothing(series, config, horizon)
+    }
+}
+
+// Fit on all but the last `holdout` values and compare the forecast of the held out values.
+// Returns mae, rmse and mape (mape skips actual values of 0).
+pub fn backtest(series: &[f64], config: &ForecastConfig, holdout: usize) -> Result<Vec<(String, f64)>, String> {
+    if holdout == 0 || holdout >= series.len() {
+        return Err("The backtest needs between 1 and n - 1 held out values".to_string());
+    }
+    let split = series.len() - holdout;
+    let predicted = forecast(&series[..split], config, holdout)?;
+    let actual = &series[split..];
+
+    let errors: Vec<f64> = actual.iter().zip(&predicted.values).map(|(a, p)| a - p).collect();
+    let relative: Vec<f64> = actual.iter().zip(&errors).filter(|(a, _)| **a != 0.0).map(|(a, e)| (e / a).abs()).collect();
+    let mut metrics = vec![("mae".to_string(), mean(&errors.iter().map(|e| e.abs()).collect::<Vec<f64>>())),
+                           ("rmse".to_string(), (sse(&errors) / errors.len() as f64).sqrt())];
+    if !relative.is_empty() {
+        metrics.push(("mape".to_string(), mean(&relative)));
+    }
+    Ok(metrics)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(method: Method) -> ForecastConfig {
+        ForecastConfig { method, alpha: 0.0, beta: 0.0, gamma: 0.0, season_length: 4, lags: 2 }
+    }
+
+    #[test]
+    fn constant_series_is_forecast_exactly() {
+        let series = vec![5.0; 20];
+        for method in [Method::SimpleExponentialSmoothing, Method::Holt, Method::HoltWinters] {
+            let result = forecast(&series, &config(method), 3).unwrap();
+            result.values.iter().for_each(|v| assert!((v - 5.0).abs() < 1e-9));
+            assert!(result.residual_std < 1e-9);
+        }
+    }
+
+    #[test]
+    fn holt_follows_a_linear_trend() {
+        let series: Vec<f64> = (0..20).map(|t| 1.0 + 2.0 * t as f64).collect();
+        let result = forecast(&series, &config(Method::Holt), 2).unwrap();
+        assert!((result.values[0] - 41.0).abs() < 1e-6);
+        assert!((result.values[1] - 43.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn holt_winters_repeats_the_season() {
+        let season = [1.0, 5.0, 3.0, 7.0];
+        let series: Vec<f64> = (0..24).map(|t| season[t % 4]).collect();
+        let result = forecast(&series, &config(Method::HoltWinters), 4).unwrap();
+        result.values.iter().zip(&season).for_each(|(v, s)| assert!((v - s).abs() < 1e-6));
+    }
+
+    #[test]
+    fn autoregression_recovers_the_coefficients() {
+        // x_t = 1 + 0.5 x_{t-1} - 0.2 x_{t-2} with a non-repeating start
+        let mut series = vec![3.0, -1.0];
+        for t in 2..30 {
+            series.push(1.0 + 0.5 * series[t - 1] - 0.2 * series[t - 2]);
+        }
+        let result = forecast(&series, &config(Method::Autoregression), 1).unwrap();
+        let coefficients: Vec<f64> = result.parameters.iter().map(|(_, v)| *v).collect();
+        [1.0, 0.5, -0.2].iter().zip(&coefficients).for_each(|(e, c)| assert!((e - c).abs() < 1e-6));
+    }
+
+    #[test]
+    fn backtest_of_a_perfect_fit_has_no_error() {
+        let series: Vec<f64> = (0..20).map(|t| 1.0 + 2.0 * t as f64).collect();
+        let metrics = backtest(&series, &config(Method::Holt), 5).unwrap();
+        metrics.iter().for_each(|(_, v)| assert!(v.abs() < 1e-6));
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub label: String,
+    pub weight: f64,
+    pub attributes: BTreeMap<String, Value>
+}
+
+// generic weighted graph between named nodes, e.g. a causal graph between time series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Graph {
+    pub directed: bool,
+    pub nodes: Vec<String>,
+    pub edges: Vec<GraphEdge>,
+    #[serde(default)]
+    pub node_attributes: BTreeMap<String, BTreeMap<String, Value>>
+}
+
+impl Graph {
+    pub fn to_dot(&self) -> String {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        // nodes and edges can be coloured by a "color" attribute
+        let color = |attributes: Option<&BTreeMap<String, Value>>| attributes.and_then(|a| a.get("color")).and_then(|c| c.as_str()).map_or_else(String::new, |c| format!(", color=\"{}\"", escape(c)));
+        let mut dot = String::from(if self.directed { "digraph {\n" } else { "graph {\n" });
+        dot.push_str("  rankdir=LR;\n  node [shape=box];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            dot.push_str(&format!("  n{} [label=\"{}\"{}];\n", i, escape(node), color(self.node_attributes.get(node))));
+        }
+        let index: BTreeMap<&str, usize> = self.nodes.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+        let arrow = if self.directed { "->" } else { "--" };
+        for edge in &self.edges {
+            if let (Some(s), Some(t)) = (index.get(edge.source.as_str()), index.get(edge.target.as_str())) {
+                dot.push_str(&format!("  n{} {} n{} [label=\"{}\"{}];\n", s, arrow, t, escape(&edge.label), color(Some(&edge.attributes))));
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+pub fn import_graph(path: &str) -> Result<Graph, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Not a graph file: {}", e))
+}
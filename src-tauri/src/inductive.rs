@@ -0,0 +1,326 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessTree {
+    Activity(String),
+    Tau,
+    Sequence(Vec<ProcessTree>),
+    Xor(Vec<ProcessTree>),
+    Parallel(Vec<ProcessTree>),
+    // first child is the do part, the rest are redo parts
+    Loop(Vec<ProcessTree>)
+}
+
+// directly-follows graph of a single (flattened) log
+#[derive(Debug, Clone, Default)]
+pub struct SimpleDfg {
+    pub activities: BTreeSet<String>,
+    pub edges: HashSet<(String, String)>,
+    pub start: BTreeSet<String>,
+    pub end: BTreeSet<String>
+}
+
+impl SimpleDfg {
+    pub fn from_traces(traces: &[Vec<&str>]) -> SimpleDfg {
+        let mut dfg = SimpleDfg::default();
+        for trace in traces.iter().filter(|t| !t.is_empty()) {
+            trace.iter().for_each(|act| {dfg.activities.insert(act.to_string());});
+            dfg.start.insert(trace[0].to_string());
+            dfg.end.insert(trace[trace.len() - 1].to_string());
+            trace.windows(2).for_each(|pair| {dfg.edges.insert((pair[0].to_string(), pair[1].to_string()));});
+        }
+        dfg
+    }
+
+    fn follows(&self, a: &str, b: &str) -> bool {
+        self.edges.contains(&(a.to_string(), b.to_string()))
+    }
+
+    // restrict the graph to a subset of activities, activities entered or left from
+    // outside the subset become start and end activities respectively
+    fn project(&self, subset: &BTreeSet<String>) -> SimpleDfg {
+        let mut projected = SimpleDfg { activities: subset.clone(), ..Default::default() };
+        for (a, b) in &self.edges {
+            match (subset.contains(a), subset.contains(b)) {
+                (true, true) => {projected.edges.insert((a.to_string(), b.to_string()));},
+                (true, false) => {projected.end.insert(a.to_string());},
+                (false, true) => {projected.start.insert(b.to_string());},
+                _ => {}
+            }
+        }
+        subset.iter().filter(|a| self.start.contains(*a)).for_each(|a| {projected.start.insert(a.to_string());});
+        subset.iter().filter(|a| self.end.contains(*a)).for_each(|a| {projected.end.insert(a.to_string());});
+        projected
+    }
+
+    fn reachability(&self) -> HashMap<&str, HashSet<&str>> {
+        let mut reach: HashMap<&str, HashSet<&str>> = self.activities.iter().map(|a| (a.as_str(), HashSet::new())).collect();
+        for start in &self.activities {
+            let mut stack: Vec<&str> = vec![start.as_str()];
+            let mut seen: HashSet<&str> = HashSet::new();
+            while let Some(curr) = stack.pop() {
+                for (a, b) in &self.edges {
+                    if a == curr && seen.insert(b.as_str()) {
+                        stack.push(b.as_str());
+                    }
+                }
+            }
+            reach.insert(start.as_str(), seen);
+        }
+        reach
+    }
+}
+
+// union-find style grouping: connects every pair of activities for which `connected` holds
+fn components<F: Fn(&str, &str) -> bool>(activities: &BTreeSet<String>, connected: F) -> Vec<BTreeSet<String>> {
+    let acts: Vec<&String> = activities.iter().collect();
+    let mut group: Vec<usize> = (0..acts.len()).collect();
+
+    fn root(group: &mut [usize], i: usize) -> usize {
+        let mut r = i;
+        while group[r] != r {
+            r = group[r];
+        }
+        group[i] = r;
+        r
+    }
+
+    for i in 0..acts.len() {
+        for j in (i + 1)..acts.len() {
+            if connected(acts[i], acts[j]) {
+                let (ri, rj) = (root(&mut group, i), root(&mut group, j));
+                group[ri] = rj;
+            }
+        }
+    }
+
+    let mut parts: HashMap<usize, BTreeSet<String>> = HashMap::new();
+    for (i, act) in acts.iter().enumerate() {
+        let r = root(&mut group, i);
+        parts.entry(r).or_default().insert(act.to_string());
+    }
+    let mut parts: Vec<BTreeSet<String>> = parts.into_values().collect();
+    parts.sort();
+    parts
+}
+
+fn xor_cut(dfg: &SimpleDfg) -> Option<Vec<BTreeSet<String>>> {
+    let parts = components(&dfg.activities, |a, b| dfg.follows(a, b) || dfg.follows(b, a));
+    if parts.len() > 1 { Some(parts) } else { None }
+}
+
+// every activity of an earlier part reaches every activity of a later part, but not the other way
+fn is_sequence(reach: &HashMap<&str, HashSet<&str>>, parts: &[BTreeSet<String>]) -> bool {
+    parts.iter().enumerate().all(|(i, earlier)| parts[i + 1..].iter().all(|later| {
+        earlier.iter().all(|a| later.iter().all(|b| reach[a.as_str()].contains(b.as_str()) && !reach[b.as_str()].contains(a.as_str())))
+    }))
+}
+
+// every part has a start and an end activity and all activities of different parts follow each other
+fn is_parallel(dfg: &SimpleDfg, parts: &[BTreeSet<String>]) -> bool {
+    let complete = |part: &BTreeSet<String>| part.iter().any(|a| dfg.start.contains(a)) && part.iter().any(|a| dfg.end.contains(a));
+    parts.iter().all(complete) && parts.iter().enumerate().all(|(i, first)| parts[i + 1..].iter().all(|second| {
+        first.iter().all(|a| second.iter().all(|b| dfg.follows(a, b) && dfg.follows(b, a)))
+    }))
+}
+
+fn sequence_cut(dfg: &SimpleDfg) -> Option<Vec<BTreeSet<String>>> {
+    let reach = dfg.reachability();
+    let reaches = |a: &str, b: &str| reach[a].contains(b);
+    // strongly connected activities and mutually unreachable activities end up in the same part
+    let parts = components(&dfg.activities, |a, b| reaches(a, b) == reaches(b, a));
+    if parts.len() < 2 {
+        return None;
+    }
+
+    let mut parts = parts;
+    // in a sequence every part reaches all of its successors, so sorting by the number of reached parts orders them
+    parts.sort_by_key(|part| {
+        let reached: usize = part.iter().map(|a| reach[a.as_str()].iter().filter(|b| !part.contains(**b)).count()).sum();
+        std::cmp::Reverse(reached)
+    });
+    if is_sequence(&reach, &parts) { Some(parts) } else { None }
+}
+
+fn parallel_cut(dfg: &SimpleDfg) -> Option<Vec<BTreeSet<String>>> {
+    let parts = components(&dfg.activities, |a, b| !(dfg.follows(a, b) && dfg.follows(b, a)));
+    if parts.len() < 2 {
+        return None;
+    }
+
+    // parts without a start or end activity are merged into another part
+    let valid = |part: &BTreeSet<String>| part.iter().any(|a| dfg.start.contains(a)) && part.iter().any(|a| dfg.end.contains(a));
+    let (mut keep, merge): (Vec<BTreeSet<String>>, Vec<BTreeSet<String>>) = parts.into_iter().partition(|p| valid(p));
+    if keep.len() < 2 {
+        return None;
+    }
+    merge.into_iter().for_each(|part| keep[0].extend(part));
+    if is_parallel(dfg, &keep) { Some(keep) } else { None }
+}
+
+fn loop_cut(dfg: &SimpleDfg) -> Option<Vec<BTreeSet<String>>> {
+    let mut do_part: BTreeSet<String> = dfg.start.union(&dfg.end).cloned().collect();
+    let rest: BTreeSet<String> = dfg.activities.difference(&do_part).cloned().collect();
+    let rest_parts = components(&rest, |a, b| dfg.follows(a, b) || dfg.follows(b, a));
+
+    let mut redo_parts: Vec<BTreeSet<String>> = vec![];
+    for part in rest_parts {
+        let entered_from_start = dfg.edges.iter().any(|(a, b)| dfg.start.contains(a) && !dfg.end.contains(a) && part.contains(b));
+        let leaves_to_end = dfg.edges.iter().any(|(a, b)| part.contains(a) && dfg.end.contains(b) && !dfg.start.contains(b));
+        let from_end = dfg.edges.iter().any(|(a, b)| dfg.end.contains(a) && part.contains(b));
+        let to_start = dfg.edges.iter().any(|(a, b)| part.contains(a) && dfg.start.contains(b));
+
+        if !entered_from_start && !leaves_to_end && from_end && to_start {
+            redo_parts.push(part);
+        } else {
+            do_part.extend(part);
+        }
+    }
+
+    if redo_parts.is_empty() {
+        return None;
+    }
+
+    let mut parts = vec![do_part];
+    parts.extend(redo_parts);
+    Some(parts)
+}
+
+fn flatten(tree: ProcessTree) -> ProcessTree {
+    match tree {
+        ProcessTree::Sequence(children) if children.len() == 1 => children.into_iter().next().unwrap(),
+        ProcessTree::Xor(children) if children.len() == 1 => children.into_iter().next().unwrap(),
+        ProcessTree::Parallel(children) if children.len() == 1 => children.into_iter().next().unwrap(),
+        t => t
+    }
+}
+
+// Inductive miner on the directly-follows graph (IMd). Cuts are tried in the order xor,
+// sequence, parallel and loop, a cut that does not hold on the graph falls through to the
+// next one and a flower model is returned when no cut can be found.
+pub fn discover_process_tree(dfg: &SimpleDfg) -> ProcessTree {
+    if dfg.activities.is_empty() {
+        return ProcessTree::Tau;
+    }
+
+    if dfg.activities.len() == 1 {
+        let act = dfg.activities.iter().next().unwrap();
+        if dfg.follows(act, act) {
+            return ProcessTree::Loop(vec![ProcessTree::Activity(act.to_string()), ProcessTree::Tau]);
+        }
+        return ProcessTree::Activity(act.to_string());
+    }
+
+    let recurse = |parts: Vec<BTreeSet<String>>| -> Vec<ProcessTree> {
+        parts.iter().map(|part| discover_process_tree(&dfg.project(part))).collect()
+    };
+
+    if let Some(parts) = xor_cut(dfg) {
+        return flatten(ProcessTree::Xor(recurse(parts)));
+    }
+    if let Some(parts) = sequence_cut(dfg) {
+        return flatten(ProcessTree::Sequence(recurse(parts)));
+    }
+    if let Some(parts) = parallel_cut(dfg) {
+        return flatten(ProcessTree::Parallel(recurse(parts)));
+    }
+    if let Some(parts) = loop_cut(dfg) {
+        return ProcessTree::Loop(recurse(parts));
+    }
+
+    // the whole graph repeats itself without any redo activities: remove the back edges
+    // from end to start activities and mine the body of the loop
+    let mut body = dfg.clone();
+    body.edges.retain(|(a, b)| !(dfg.end.contains(a) && dfg.start.contains(b)));
+    if body.edges.len() < dfg.edges.len() {
+        return ProcessTree::Loop(vec![discover_process_tree(&body), ProcessTree::Tau]);
+    }
+
+    // flower model
+    let mut children = vec![ProcessTree::Tau];
+    children.push(ProcessTree::Xor(dfg.activities.iter().map(|a| ProcessTree::Activity(a.to_string())).collect()));
+    ProcessTree::Loop(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(a: &str) -> ProcessTree {
+        ProcessTree::Activity(a.to_string())
+    }
+
+    fn part(activities: &[&str]) -> BTreeSet<String> {
+        activities.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn dfg(edges: &[(&str, &str)], start: &[&str], end: &[&str]) -> SimpleDfg {
+        let mut dfg = SimpleDfg { start: part(start), end: part(end), ..Default::default() };
+        for (a, b) in edges {
+            dfg.activities.extend([a.to_string(), b.to_string()]);
+            dfg.edges.insert((a.to_string(), b.to_string()));
+        }
+        dfg
+    }
+
+    #[test]
+    fn sequence() {
+        let tree = discover_process_tree(&SimpleDfg::from_traces(&[vec!["a", "b", "c"]]));
+        assert_eq!(tree, ProcessTree::Sequence(vec![activity("a"), activity("b"), activity("c")]));
+    }
+
+    #[test]
+    fn xor() {
+        let tree = discover_process_tree(&SimpleDfg::from_traces(&[vec!["a"], vec!["b"]]));
+        assert_eq!(tree, ProcessTree::Xor(vec![activity("a"), activity("b")]));
+    }
+
+    #[test]
+    fn parallel() {
+        let tree = discover_process_tree(&SimpleDfg::from_traces(&[vec!["a", "b"], vec!["b", "a"]]));
+        assert_eq!(tree, ProcessTree::Parallel(vec![activity("a"), activity("b")]));
+    }
+
+    #[test]
+    fn redo_loop() {
+        // b is never a start or end activity, so the parallel cut {a}, {b} does not hold
+        let tree = discover_process_tree(&SimpleDfg::from_traces(&[vec!["a", "b", "a"], vec!["a"]]));
+        assert_eq!(tree, ProcessTree::Loop(vec![activity("a"), activity("b")]));
+    }
+
+    #[test]
+    fn sequence_in_loop_body() {
+        let tree = discover_process_tree(&SimpleDfg::from_traces(&[vec!["a", "b", "a", "b"]]));
+        assert_eq!(tree, ProcessTree::Loop(vec![ProcessTree::Sequence(vec![activity("a"), activity("b")]), ProcessTree::Tau]));
+    }
+
+    #[test]
+    fn flower_without_cut() {
+        let dfg = dfg(&[("a", "b"), ("b", "a"), ("a", "c"), ("c", "a")], &["b"], &["c"]);
+        let tree = discover_process_tree(&dfg);
+        assert_eq!(tree, ProcessTree::Loop(vec![ProcessTree::Tau, ProcessTree::Xor(vec![activity("a"), activity("b"), activity("c")])]));
+    }
+
+    #[test]
+    fn sequence_cut_needs_strict_order() {
+        let dfg = dfg(&[("a", "b"), ("b", "c")], &["a"], &["c"]);
+        let reach = dfg.reachability();
+        assert!(is_sequence(&reach, &[part(&["a"]), part(&["b"]), part(&["c"])]));
+        assert!(!is_sequence(&reach, &[part(&["b"]), part(&["a"]), part(&["c"])]));
+        assert!(!is_sequence(&reach, &[part(&["a", "c"]), part(&["b"])]));
+    }
+
+    #[test]
+    fn parallel_cut_needs_start_end_and_all_edges() {
+        let dfg = dfg(&[("a", "b"), ("b", "a"), ("b", "c"), ("c", "b")], &["a", "b"], &["a", "b"]);
+        assert!(is_parallel(&dfg, &[part(&["a", "c"]), part(&["b"])]));
+        // c misses a start and end activity
+        assert!(!is_parallel(&dfg, &[part(&["a"]), part(&["b"]), part(&["c"])]));
+        // a and c never follow each other
+        let mut complete = dfg.clone();
+        complete.start.insert("c".to_string());
+        complete.end.insert("c".to_string());
+        assert!(!is_parallel(&complete, &[part(&["a"]), part(&["b"]), part(&["c"])]));
+    }
+}
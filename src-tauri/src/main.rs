@@ -3,22 +3,54 @@
   windows_subsystem = "windows"
 )]
 
-use std::{collections::{HashMap, HashSet}, sync::atomic::{AtomicUsize, Ordering}, path::Path, fs::OpenOptions, error::Error};
-use pmrs::{objects::{ocel::{importer::import_ocel, exporter::{export_ocel_pretty, generate_ocel_external_repr}, OcelSerde}, ocdg::{Ocdg, generate_ocdg, Relations, importer::import_ocdg, exporter::export_ocdg}}, algo::transformation::ocel::{features::{object_point::{object_point_features, ObjectPointConfig, ObjectPoint}, object_group::{ObjectGroup, ObjectGroupConfig, object_group_features}, event_point::{EventPoint, event_point_features, EventPointConfig}, event_group::{EventGroup, event_group_features, EventGroupConfig}, operator::Operator}, timeseries::{generate_time_series, TimeSeries, auto_timediff_binning}, situations::{object_situations::{ObjectSituations, ObjectSituationParameters}, event_situations::{EventSituations, EventSituationParameters}}}};
-use polars::{prelude::{Series, DataFrame, NamedFrom, CsvWriter}, io::SerWriter};
+use std::{collections::{HashMap, HashSet, BTreeMap, BTreeSet}, sync::atomic::{AtomicUsize, Ordering}, path::Path, fs::OpenOptions, error::Error};
+use pmrs::{objects::{ocel::{importer::import_ocel, exporter::{export_ocel_pretty, generate_ocel_external_repr}, OcelSerde}, ocdg::{Ocdg, generate_ocdg, Relations, importer::import_ocdg, exporter::export_ocdg}}, algo::transformation::ocel::{features::{object_point::{object_point_features, ObjectPointConfig, ObjectPoint}, object_group::{ObjectGroup, ObjectGroupConfig, object_group_features}, event_point::{EventPoint, event_point_features, EventPointConfig}, event_group::{EventGroup, event_group_features, EventGroupConfig}, operator::Operator}, situations::{object_situations::{ObjectSituations, ObjectSituationParameters}, event_situations::{EventSituations, EventSituationParameters}}}};
+use polars::{prelude::{Series, DataFrame, NamedFrom, CsvWriter, ParquetWriter}, io::SerWriter};
 use serde::{Serialize, Deserialize};
 use strum::{IntoEnumIterator, EnumIter, EnumString};
 use pmrs::objects::ocel::Ocel;
 use pmrs::objects::ocel::validator::validate_ocel_verbose;
 use pmrs::objects::ocdg::exporter::generate_ocdg_string;
+use dfg::{OcDfg, discover_ocdfg};
+use petri_net::{OcPetriNet, discover_ocpn, tree_to_string, import_pnml};
+use provenance::ColumnProvenance;
+use model::{Model, import_model};
+use graph::{Graph, import_graph};
 use tauri::Manager;
 use std::str::FromStr;
 use serde_json::{Value, Map, json};
 use std::sync::Mutex;
 use std::fs;
-use chrono::Local;
+use chrono::{Local, Duration};
 use rayon::prelude::*;
-use itertools::Itertools;
+
+mod statistics;
+mod filtering;
+mod dfg;
+mod inductive;
+mod petri_net;
+mod conformance;
+mod executions;
+mod features;
+mod provenance;
+mod tables;
+mod rng;
+mod tree;
+mod model;
+mod clustering;
+mod anomaly;
+mod time_series;
+mod numeric;
+mod forecasting;
+mod drift;
+mod causality;
+mod graph;
+mod object_graph;
+mod ocdg_export;
+mod centrality;
+mod community;
+#[cfg(test)]
+mod fixtures;
 
 static COUNTER: AtomicUsize = AtomicUsize::new(1);
 
@@ -30,13 +62,21 @@ fn get_new_id() -> usize {
 enum Entity {
     Ocel(OcelEntity),
     Ocdg(OcdgEntity),
-    Table(TableEntity)
+    Table(TableEntity),
+    Dfg(DfgEntity),
+    PetriNet(PetriNetEntity),
+    Model(ModelEntity),
+    Graph(GraphEntity)
 }
 
 enum EntityPrimitive<'a> {
     Ocel(&'a Ocel),
     Ocdg(&'a Ocdg),
-    Table(&'a DataFrame)
+    Table(&'a DataFrame),
+    Dfg(&'a OcDfg),
+    PetriNet(&'a OcPetriNet),
+    Model(&'a Model),
+    Graph(&'a Graph)
 }
 
 #[derive(Debug, EnumIter, EnumString, Clone)]
@@ -52,6 +92,27 @@ enum Plugins {
     OcelTimeSeries,
     OcelObjectSituations,
     OcelEventSituations,
+    DescribeOcel,
+    FilterOcel,
+    DiscoverOcDfg,
+    DiscoverOcPetriNet,
+    TokenReplay,
+    ProcessExecutions,
+    ObjectPointFeatures,
+    AttributeOperatorFeatures,
+    EventGroupFeatures,
+    SituationTable,
+    DecisionTree,
+    ApplyModel,
+    Clustering,
+    AnomalyDetection,
+    Forecasting,
+    DriftDetection,
+    Causality,
+    OcdgCentrality,
+    OcdgCommunities,
+    OcdgSubgraph,
+    OcdgDiff,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -90,17 +151,16 @@ fn share_progress(current_task: &str, current_step: &mut u8, total_steps: u8, ha
 }
 
 #[tauri::command]
-fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntityState>, handler: tauri::AppHandle) -> Result<String, String> {
+fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntityState>, handler: tauri::AppHandle) -> Result<Vec<String>, String> {
     let plugin: Plugins = Plugins::from_str(&params.enumid).unwrap();
     let plugin_info: Plugin = get_plugin_info(&plugin).expect("Plugin was not implemented properly.");
     let mut curr_step: u8 = 1;
     let total_steps: u8 = plugin_info.total_steps + 2;
     share_progress(format!("Starting Plugin: {}", plugin_info.name.as_str()).as_str(), &mut curr_step, total_steps, &handler);
     let id = get_new_id();
-    let mut metadata = Map::<String, Value>::new();
+    let mut output_ids: Vec<usize> = vec![id];
+    let mut metadata = generate_default_metadata(id);
     let mut instancedata = Map::<String, Value>::new();
-    metadata.entry("rust-id".to_string()).or_insert(Value::String(id.to_string()));
-    metadata.entry("time-created".to_string()).or_insert(Value::String(Local::now().to_string()));
 
     match plugin {
         Plugins::GenerateOcdg => {
@@ -120,7 +180,7 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     metadata.entry("type".to_string()).or_insert(Value::String("ocdg".to_string()));
                     metadata.entry("type-long".to_string()).or_insert(Value::String("Object-Centric Directed Graph".to_string()));
                     share_progress("Storing OCDG", &mut curr_step, total_steps, &handler);
-                    let new_ocdg = OcdgEntity {id, object: ocdg, metadata, instancedata};
+                    let new_ocdg = OcdgEntity {id, object: ocdg, metadata, instancedata, diff: None};
                     state.entry(id).or_insert(Entity::Ocdg(new_ocdg)); 
 
                 }
@@ -148,7 +208,8 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     df = DataFrame::new(vec![Series::new("Error Reason", err_reason), Series::new("Error Location", err_location)]).expect("Data Table Creation went wrong");
 
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table = TableEntity{id, object: df, metadata, instancedata};
+                    let columns = provenance::table_provenance(&df, "ValidateOcel", &[]);
+                    let new_table = TableEntity{id, object: df, metadata, instancedata, columns};
                     state.entry(id).or_insert(Entity::Table(new_table));
                 },
                 Err(error) => {
@@ -163,55 +224,15 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
             let mut state = entitystate.0.lock().unwrap();
             if let Entity::Ocel(ocel) = &state[&iocel] {
                 if let Entity::Ocdg(ocdg) = &state[&iocdg] {
-                    // let all_op: Vec<String> = Operator::iter().map(|op| op.to_string()).collect();
-                    // let all_attr_val: &Vec<Value> = ocel.object.global_log.get("ocel:attribute-names").unwrap().as_array().unwrap();
-                    let all_otypes_val: &Vec<Value> = ocel.object.global_log.get("ocel:object-types").unwrap().as_array().unwrap();
-                    let all_rels: Vec<Relations> = Relations::iter().collect();
-
-                    let mut params: Vec<(ObjectPoint, Option<Value>)> = vec![];
-                    params.push((ObjectPoint::UniqueNeighborCount, None));
-                    params.push((ObjectPoint::ActivityExistence, None));
-                    params.push((ObjectPoint::ActivityExistenceCount, None));
-                    params.push((ObjectPoint::ObjectLifetime, None));
-                    params.push((ObjectPoint::ObjectEventInteractionOperator, None));
-                    params.push((ObjectPoint::ObjectUnitSetRatio, None));
-                    params.push((ObjectPoint::ObjectEventsDirectlyFollows, None));
-                    params.push((ObjectPoint::ObjectInputs, None));
-                    params.push((ObjectPoint::ObjectOutputs, None));
-
-                    // all rels
-                    all_rels.iter()
-                            .for_each(|rel| {
-                                params.push((ObjectPoint::ObjectDirectRelationCount, Some(json!({"relations": format!("{:?}", rel).as_str()}))));
-                            });
-
-                    // double act
-                    ocel.object.activities.iter()
-                                          .cartesian_product(&ocel.object.activities)
-                                          .for_each(|(a1, a2)| {
-                                            params.push((ObjectPoint::ObjectWaitTime, Some(json!({"activity_src": a1, "activity_tar": a2}))));
-                                          });
-
-                    // otypes
-                    all_otypes_val.iter()
-                                  .for_each(|ot| {
-                                    params.push((ObjectPoint::ObjectTypeInteraction, Some(json!({"object_type": ot}))));
-                                  });
-
-
-                    // all_op.iter()
-                    //       .for_each(|op| {
-                    //           all_attr_val.iter()
-                    //                       .for_each(|attr| {
-                    //                           params.push((ObjectPoint::Activi));
-                    //                       });
-                    // });
-
-
-
-                    let feature_config: ObjectPointConfig = ObjectPointConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: &params };
+                    let params: Vec<(ObjectPoint, Option<Value>)> = match features::all_object_point_params(&ocel.object) {
+                        Ok(params) => params,
+                        Err(e) => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err(e);
+                        }
+                    };
                     share_progress("Extracting Object Point Features", &mut curr_step, total_steps, &handler);
-                    let df: DataFrame = object_point_features(feature_config);
+                    let (df, columns) = provenance::build_features(params, "ObjectPoint", &[iocel, iocdg], |part| object_point_features(ObjectPointConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: part }))?;
                     share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
                     metadata.entry("name".to_string()).or_insert(json!(format!("Object Point Features {:?}", &id)));
                     metadata.entry("type".to_string()).or_insert(json!("table"));
@@ -220,7 +241,7 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
 
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table = TableEntity {id, object: df, metadata, instancedata};
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
                     state.entry(id).or_insert(Entity::Table(new_table));
                 }
             }
@@ -236,6 +257,7 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     let df: DataFrame = table.object.clone();
                     let cols = df.get_columns();
                     let oids: Vec<&str> = cols[0].utf8().unwrap().into_no_null_iter().collect();
+                    let attributes = provenance::merge_provenance(&ocel.attributes, &table.columns);
                     for col_id in 1..cols.len() {
                         let curr_series = &cols[col_id];
                         let curr_name = curr_series.name();
@@ -251,7 +273,7 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     metadata.entry("file-type".to_string()).or_insert(Value::String("jsonocel".to_string()));
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocel(&new_ocel)));
 
-                    let new_ocel: OcelEntity = OcelEntity { id, object: new_ocel, metadata, instancedata };
+                    let new_ocel: OcelEntity = OcelEntity { id, object: new_ocel, metadata, instancedata, attributes };
                     state.entry(id).or_insert(Entity::Ocel(new_ocel));
 
                 }
@@ -272,26 +294,95 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
             let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
             let mut state = entitystate.0.lock().unwrap();
             if let Entity::Ocel(ocel) = &state[&iocel] {
-                if let Value::String(ts_type_str) = &params.parameters[0]["dropdown:SelectSeriesType"] {
-                    let ts_type = TimeSeries::from_str(ts_type_str.as_str()).expect("should never fail");
-                    let binning = auto_timediff_binning(&ocel.object);
-                    share_progress("Generating time series", &mut curr_step, total_steps, &handler);
-                    let series_vec: Vec<f64> = generate_time_series::<f64>(&ocel.object, binning, ts_type);
-                    let series = Series::new(format!("Time Series:{}:{}", ocel.id, ts_type_str).as_str(), series_vec);
-                    share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
-                    metadata.entry("name".to_string()).or_insert(json!(format!("{} of {:?}", ts_type_str, ocel.metadata["name"].as_str().unwrap())));
-                    metadata.entry("type".to_string()).or_insert(json!("table"));
-                    metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
-                    instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
-                    let df = DataFrame::new(vec![series]).expect("cannot fail!");
-                    instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-
-                    let new_table: TableEntity = TableEntity { id, object: df, metadata, instancedata };
-                    state.entry(id).or_insert(Entity::Table(new_table));
-
+                // older frontends still send the single dropdown:SelectSeriesType
+                let series_types: Vec<&str> = match (params.parameters[0]["multichoice:SeriesTypes"].as_array(), params.parameters[0]["dropdown:SelectSeriesType"].as_str()) {
+                    (Some(types), _) if !types.is_empty() => types.iter().filter_map(|t| t.as_str()).collect(),
+                    (_, Some(series_type)) => vec![series_type],
+                    _ => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("Invalid Input Series Types".to_string());
+                    }
+                };
+                let breakdown = params.parameters[0]["dropdown:Breakdown"].as_str().unwrap_or("None");
+
+                let (start, end) = match filtering::parse_time_bounds(&params.parameters[0]) {
+                    Ok(bounds) => bounds,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                let width = match params.parameters[0]["string:BinWidth"].as_str() {
+                    Some(width) if !width.is_empty() => match time_series::parse_duration(width) {
+                        Ok(w) => Some(w),
+                        Err(e) => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err(e);
+                        }
+                    },
+                    _ => None
+                };
+                let bin_count = params.parameters[0]["number:BinCount"].as_f64().filter(|c| *c >= 1.0).map(|c| c as usize);
+                if let Some(unknown) = series_types.iter().find(|t| time_series::parse_series_type(t).is_err()) {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err(format!("Unknown time series type {}", unknown));
                 }
+
+                // without a bin width or count, the automatic binning of pmrs is used on the events
+                // between StartTime and EndTime
+                let explicit = width.is_some() || bin_count.is_some();
+                let mut series: Vec<Series> = vec![];
+                share_progress("Generating time series", &mut curr_step, total_steps, &handler);
+                let generated = if explicit {
+                    let binning = match time_series::Binning::new(&ocel.object, start, end, width, bin_count) {
+                        Ok(binning) => binning,
+                        Err(e) => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err(e);
+                        }
+                    };
+                    instancedata.entry("Bin Width (s)".to_string()).or_insert(json!(binning.width.num_milliseconds() as f64 / 1000.0));
+                    instancedata.entry("Bin #".to_string()).or_insert(json!(binning.bins));
+                    series.push(Series::new("Timestamp", binning.timestamps()));
+                    time_series::generate_series(&ocel.object, &binning, &series_types, breakdown)?
+                } else {
+                    let windowed: Option<Ocel> = match (start, end) {
+                        (None, None) => None,
+                        (start, end) => {
+                            let events: HashSet<usize> = ocel.object.events.iter()
+                                                                    .filter(|(_, ev)| start.map_or(true, |s| ev.timestamp >= s) && end.map_or(true, |e| ev.timestamp <= e))
+                                                                    .map(|(eid, _)| *eid)
+                                                                    .collect();
+                            let objects: HashSet<usize> = events.iter().flat_map(|eid| ocel.object.events[eid].omap.iter().copied()).collect();
+                            Some(filtering::extract_sublog(&ocel.object, &events, &objects))
+                        }
+                    };
+                    let generated = time_series::generate_auto_series(windowed.as_ref().unwrap_or(&ocel.object), &series_types, breakdown)?;
+                    let bins = generated.first().map_or(0, |ts| ts.values.len());
+                    instancedata.entry("Binning".to_string()).or_insert(json!("auto"));
+                    instancedata.entry("Bin #".to_string()).or_insert(json!(bins));
+                    series.push(Series::new("Bin", (0..bins as u32).collect::<Vec<u32>>()));
+                    generated
+                };
+                share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                generated.iter().for_each(|ts| series.push(Series::new(ts.name.as_str(), ts.values.clone())));
+                let df = DataFrame::new(series).expect("Data Table Creation went wrong");
+
+                let mut columns = provenance::table_provenance(&df, "TimeSeries", &[iocel]);
+                columns.iter_mut().skip(1).zip(&generated).for_each(|(col, ts)| {
+                    col.variant = Some(ts.series_type.to_string());
+                    col.params = ts.params.clone();
+                });
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("{} of {:?}", series_types.join(", "), ocel.metadata["name"].as_str().unwrap())));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                instancedata.entry("Breakdown".to_string()).or_insert(json!(breakdown));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let new_table: TableEntity = TableEntity { id, object: df, metadata, instancedata, columns };
+                state.entry(id).or_insert(Entity::Table(new_table));
             }
-        
         },
         Plugins::OcelObjectSituations => {
             let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
@@ -335,8 +426,11 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                                 return Err("Invalid Input Activities".to_string());
                             }
                         },
-                        _ => {
-                            return Err("Not yet implemented".to_string());
+                        ObjectSituations::ObjectMissingReachableObjectType => {
+                            if sit_params.object_types.is_none() {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err("Invalid Input Object Types".to_string());
+                            }
                         }
                     }
 
@@ -355,7 +449,9 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
                     instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table: TableEntity = TableEntity { id, object: df, metadata, instancedata };
+                    let mut columns = provenance::table_provenance(&df, "ObjectSituations", &[iocel]);
+                    columns[1].variant = Some(sit_type_str.to_string());
+                    let new_table: TableEntity = TableEntity { id, object: df, metadata, instancedata, columns };
                     state.entry(id).or_insert(Entity::Table(new_table));
                     
                 }
@@ -392,11 +488,27 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     let input_relations: Vec<Relations>;
                     if let Some(relations) = &params.parameters[0]["string:Relations"].as_str() {
                         if !relations.is_empty() {
-                            input_relations = relations.split(";").into_iter().map(|s| Relations::from_str(s).unwrap()).collect();
+                            input_relations = match relations.split(";").map(Relations::from_str).collect::<Result<Vec<Relations>, _>>() {
+                                Ok(rels) => rels,
+                                Err(_) => {
+                                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                    return Err("Invalid Input Relations".to_string());
+                                }
+                            };
                             sit_params.relations = Some(input_relations.iter().collect());
                         }
                     }
 
+                    // durations above the threshold (in seconds) become 1, the rest 0. No threshold keeps the raw durations
+                    let duration_threshold: Option<f64> = match params.parameters[0]["number:DurationThreshold"].as_f64() {
+                        Some(t) if t < 0.0 => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err("Invalid Input Duration Threshold".to_string());
+                        },
+                        Some(t) if t > 0.0 => Some(t),
+                        _ => None
+                    };
+
                     match sit_type {
                         EventSituations::EventAttribute | EventSituations::EventAttributeUnknown => {
                             if sit_params.property.is_none() {
@@ -422,15 +534,28 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                                 return Err("Invalid Input Activities".to_string());
                             }
                         },
-                        _ => {
-                            return Err("Not yet implemented".to_string());
+                        EventSituations::EventWait | EventSituations::EventDuration => {
+                            if sit_params.activities.is_none() {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err("Invalid Input Activities".to_string());
+                            }
+                        },
+                        EventSituations::EventMissingRelation => {
+                            if sit_params.relations.is_none() {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err("Invalid Input Relations".to_string());
+                            }
                         }
                     }
 
+                    let is_duration = matches!(sit_type, EventSituations::EventWait | EventSituations::EventDuration);
                     target_vec.par_iter_mut().enumerate().for_each(|(i, val)| *val = sit_type.execute(&ocel.object, &sit_params, &i));
                     let target_series: Series = Series::new(format!("{:?}", sit_type).as_str(), target_vec.iter().map(|val| {
                         if let Some(exists) = val {
-                            return exists.as_f64()
+                            return match (is_duration, duration_threshold) {
+                                (true, Some(threshold)) => exists.as_f64().map(|d| if d > threshold { 1.0 } else { 0.0 }),
+                                _ => exists.as_f64()
+                            };
                         }
                         None
                     }).collect::<Vec<Option<f64>>>());
@@ -442,7 +567,12 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
                     instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table: TableEntity = TableEntity { id, object: df, metadata, instancedata };
+                    let mut columns = provenance::table_provenance(&df, "EventSituations", &[iocel]);
+                    columns[1].variant = Some(sit_type_str.to_string());
+                    if let (true, Some(threshold)) = (is_duration, duration_threshold) {
+                        columns[1].params = Some(json!({"threshold": threshold}));
+                    }
+                    let new_table: TableEntity = TableEntity { id, object: df, metadata, instancedata, columns };
                     state.entry(id).or_insert(Entity::Table(new_table));
                 }
             }
@@ -464,9 +594,8 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     
 
                     // let params: HashMap<ObjectGroup, Option<Value>> = HashMap::from_iter(param_vec);
-                    let feature_config: EventPointConfig = EventPointConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: &params };
                     share_progress("Extracting Event Point Features", &mut curr_step, total_steps, &handler);
-                    let df: DataFrame = event_point_features(feature_config);
+                    let (df, columns) = provenance::build_features(params, "EventPoint", &[iocel, iocdg], |part| event_point_features(EventPointConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: part }))?;
                     share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
                     metadata.entry("name".to_string()).or_insert(json!(format!("Event Point Features {:?}", &id)));
                     metadata.entry("type".to_string()).or_insert(json!("table"));
@@ -475,7 +604,7 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
 
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table = TableEntity {id, object: df, metadata, instancedata};
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
                     state.entry(id).or_insert(Entity::Table(new_table));
                 }
             }
@@ -505,9 +634,8 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     });
 
                     // let params: HashMap<ObjectGroup, Option<Value>> = HashMap::from_iter(param_vec);
-                    let feature_config: EventGroupConfig = EventGroupConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: &params };
                     share_progress("Extracting Event Group Features", &mut curr_step, total_steps, &handler);
-                    let df: DataFrame = event_group_features(feature_config);
+                    let (df, columns) = provenance::build_features(params, "EventGroup", &[iocel, iocdg], |part| event_group_features(EventGroupConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: part }))?;
                     share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
                     metadata.entry("name".to_string()).or_insert(json!(format!("Event Group Features {:?}", &id)));
                     metadata.entry("type".to_string()).or_insert(json!("table"));
@@ -516,7 +644,7 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
 
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table = TableEntity {id, object: df, metadata, instancedata};
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
                     state.entry(id).or_insert(Entity::Table(new_table));
                 }
             }
@@ -550,9 +678,8 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
 
 
                     // let params: HashMap<ObjectGroup, Option<Value>> = HashMap::from_iter(param_vec);
-                    let feature_config: ObjectGroupConfig = ObjectGroupConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: &params };
                     share_progress("Extracting Object Group Features", &mut curr_step, total_steps, &handler);
-                    let df: DataFrame = object_group_features(feature_config);
+                    let (df, columns) = provenance::build_features(params, "ObjectGroup", &[iocel, iocdg], |part| object_group_features(ObjectGroupConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: part }))?;
                     share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
                     metadata.entry("name".to_string()).or_insert(json!(format!("Object Group Features {:?}", &id)));
                     metadata.entry("type".to_string()).or_insert(json!("table"));
@@ -561,214 +688,2033 @@ fn activate_plugin(params: PluginParameters, entitystate: tauri::State<EntitySta
                     instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
 
                     instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
-                    let new_table = TableEntity {id, object: df, metadata, instancedata};
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
                     state.entry(id).or_insert(Entity::Table(new_table));
                 }
             }
 
         }, 
-        // _ => {},
-    }
+        Plugins::DescribeOcel => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let ocel_name = ocel.metadata["name"].as_str().unwrap().to_string();
+                share_progress("Describing OCEL", &mut curr_step, total_steps, &handler);
+                let description = statistics::describe_ocel(&ocel.object);
+                share_progress("Storing Results as DataFrames", &mut curr_step, total_steps, &handler);
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("Activity Frequencies of {:?}", ocel_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel_name));
+                instancedata.extend(description.summary);
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&description.activities)));
+                let columns = provenance::table_provenance(&description.activities, "DescribeOcel", &[iocel]);
+                let new_table = TableEntity {id, object: description.activities, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let used = Map::from_iter([("ocel-used".to_string(), json!(ocel_name))]);
+                for (name, df) in [("Object Type Counts", description.object_types),
+                                   ("Events per Object", description.events_per_object),
+                                   ("Objects per Event", description.objects_per_event),
+                                   ("Attribute Completeness", description.attribute_completeness),
+                                   ("Activity Object Type Co-occurrence", description.activity_object_types)] {
+                    output_ids.push(store_table(format!("{} of {:?}", name, ocel_name), df, used.clone(), "DescribeOcel", &[iocel], &mut state));
+                }
+            }
+        },
+        Plugins::FilterOcel => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let mut filter = filtering::OcelFilter::default();
+                let split_input = |key: &str| -> Option<HashSet<&str>> {
+                    match params.parameters[0][key].as_str() {
+                        Some(input) if !input.is_empty() => Some(HashSet::from_iter(input.split(";"))),
+                        _ => None
+                    }
+                };
 
-    share_progress(format!("Finished Plugin: {}", plugin_info.name.as_str()).as_str(), &mut curr_step, total_steps, &handler);
-    Ok(id.to_string())
-}
+                filter.include_activities = split_input("string:IncludeActivities");
+                filter.exclude_activities = split_input("string:ExcludeActivities");
+                filter.include_object_types = split_input("string:IncludeObjectTypes");
+                filter.exclude_object_types = split_input("string:ExcludeObjectTypes");
 
-fn get_plugin_info(plugin: &Plugins) -> Option<Plugin> {
-    match plugin {
-        Plugins::GenerateOcdg => {
-            let plug = r#"{
-                "id": 1,
-                "name": "Generate Ocdg",
-                "total_steps": 2,
-                "enumid": "GenerateOcdg",
-                "description": "Generate an Object-Centric Directed Graph with specified relations.",
-                "type": "Generation",
-                "input": {"ocel": 1},
-                "output": {"ocdg": 1},
-                "parameters": []
-            }"#;
-            let mut gen_ocdg: Plugin = serde_json::from_str(plug).unwrap();
-            let parameters: HashMap<String, Value> = HashMap::from([("multichoice:Relations".to_string(), serde_json::to_value(Relations::iter().map(|rel| format!("{:?}", rel)).collect::<Vec<String>>()).unwrap()), ("header".to_string(), Value::String("General".to_string()))]);
-            gen_ocdg.parameters.push(parameters);
-            return Some(gen_ocdg);
-        },
-        Plugins::ValidateOcel => {
-            let plug = r#"{
-                "id": 2,
-                "name": "Validate Ocel",
-                "total_steps": 2,
-                "enumid": "ValidateOcel",
-                "description": "Validates the OCEL input file and returns all errors that exist with the document",
-                "type": "Validation",
-                "input": {},
-                "output": {"table": 1},
-                "parameters": [{"header": "General", "file:ValidationFile": ""}]
-            }"#;
+                match filtering::parse_time_bounds(&params.parameters[0]) {
+                    Ok((start, end)) => {
+                        filter.start = start;
+                        filter.end = end;
+                    },
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                }
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
-        },
-        Plugins::AllObjectPointFeatures => {
-        let plug = r#"{
-                "id": 3,
-                "name": "Generate all Object Point Features (oid intersection)",
-                "total_steps": 2,
-                "enumid": "AllObjectPointFeatures",
-                "description": "Generate all object features based on default values. This plugin only returns features of objects that are in both the ocel and ocdg.",
-                "type": "Feature Extraction",
-                "input": {"ocel": 1, "ocdg": 1},
-                "output": {"table": 1},
-                "parameters": []
-            }"#;
+                if let Some(predicates) = params.parameters[0]["string:AttributePredicates"].as_str() {
+                    for predicate in predicates.split(";").filter(|p| !p.is_empty()) {
+                        match filtering::AttributePredicate::parse(predicate) {
+                            Ok(pred) => {filter.predicates.push(pred);},
+                            Err(e) => {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err(e);
+                            }
+                        }
+                    }
+                }
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
+                share_progress("Filtering OCEL", &mut curr_step, total_steps, &handler);
+                let new_ocel = filtering::filter_ocel(&ocel.object, &filter);
+                share_progress("Storing new OCEL log", &mut curr_step, total_steps, &handler);
+                metadata.entry("name".to_string()).or_insert(json!(format!("Filtered {}", ocel.metadata["name"].as_str().unwrap())));
+                metadata.entry("type".to_string()).or_insert(Value::String("ocel".to_string()));
+                metadata.entry("type-long".to_string()).or_insert(Value::String("Object-Centric Event Log".to_string()));
+                metadata.entry("file-type".to_string()).or_insert(Value::String("jsonocel".to_string()));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                instancedata.entry("Filter".to_string()).or_insert(json!(params.parameters[0]));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocel(&new_ocel)));
+
+                let new_ocel: OcelEntity = OcelEntity { id, object: new_ocel, metadata, instancedata, attributes: ocel.attributes.clone() };
+                state.entry(id).or_insert(Entity::Ocel(new_ocel));
+            }
         },
-        Plugins::MergeFeaturesIntoOcel => {
-        let plug = r#"{
-                "id": 4,
-                "name": "Merge Feature Table into Ocel log",
-                "total_steps": 2,
-                "enumid": "MergeFeaturesIntoOcel",
-                "description": "Merge a feature table into an ocel log. If ConsumeEntities is true, the input objects are consumed to create the merged log. If false, the result is the result of cloning the data.",
-                "type": "Combination",
-                "input": {"ocel": 1, "table": 1},
-                "output": {"ocel": 1},
-                "parameters": [{"header": "General", "bool:ConsumeEntities": true}]
-            }"#;
+        Plugins::DiscoverOcDfg => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let min_frequency = params.parameters[0]["number:MinFrequency"].as_f64().unwrap_or(1.0);
+                share_progress("Discovering Object-Centric DFG", &mut curr_step, total_steps, &handler);
+                let mut ocdfg: OcDfg = discover_ocdfg(&ocel.object);
+                if min_frequency > 1.0 {
+                    ocdfg.filter_frequency(min_frequency as u32);
+                }
+                share_progress("Storing Object-Centric DFG", &mut curr_step, total_steps, &handler);
+                metadata.entry("name".to_string()).or_insert(json!(format!("OC-DFG of {}", ocel.metadata["name"].as_str().unwrap())));
+                metadata.entry("type".to_string()).or_insert(json!("dfg"));
+                metadata.entry("type-long".to_string()).or_insert(json!("Object-Centric Directly-Follows Graph"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                instancedata.entry("Minimum Frequency".to_string()).or_insert(json!(min_frequency));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Dfg(&ocdfg)));
+                let new_dfg = DfgEntity {id, object: ocdfg, metadata, instancedata};
+                state.entry(id).or_insert(Entity::Dfg(new_dfg));
+            }
+        },
+        Plugins::DiscoverOcPetriNet => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let mut object_types: Vec<String> = statistics::get_object_types(&ocel.object);
+                if let Some(input_otypes) = params.parameters[0]["string:ObjectTypes"].as_str() {
+                    if !input_otypes.is_empty() {
+                        let selected: HashSet<&str> = HashSet::from_iter(input_otypes.split(";"));
+                        object_types.retain(|ot| selected.contains(ot.as_str()));
+                    }
+                }
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
+                if object_types.is_empty() {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err("Invalid Input Object Types".to_string());
+                }
 
-            
+                share_progress("Discovering Object-Centric Petri Net", &mut curr_step, total_steps, &handler);
+                let (ocpn, trees) = discover_ocpn(&ocel.object, &object_types);
+                share_progress("Storing Object-Centric Petri Net", &mut curr_step, total_steps, &handler);
+                metadata.entry("name".to_string()).or_insert(json!(format!("OCPN of {}", ocel.metadata["name"].as_str().unwrap())));
+                metadata.entry("type".to_string()).or_insert(json!("petrinet"));
+                metadata.entry("type-long".to_string()).or_insert(json!("Object-Centric Petri Net"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                instancedata.entry("Process Trees".to_string()).or_insert(json!(trees.iter().map(|(ot, tree)| (ot.to_string(), tree_to_string(tree))).collect::<HashMap<String, String>>()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::PetriNet(&ocpn)));
+                let new_net = PetriNetEntity {id, object: ocpn, metadata, instancedata};
+                state.entry(id).or_insert(Entity::PetriNet(new_net));
+            }
         },
-        Plugins::UiDemo => {
-        let plug = r#"{
-                "id": 5,
-                "name": "UI Demo",
-                "total_steps": 0,
-                "enumid": "UiDemo",
-                "description": "Plugin to showcase all the user interface options that are available.",
-                "type": "Demo",
-                "input": {},
-                "output": {},
-                "parameters": [{"header": "General", "string:Normal String": "default input", "number: Number input": 123.321, "multichoice:Multiple Choice!!": ["multiple", "selection", "options"], "bool:boolean selection": false, "file:Select a file!": "", "dropdown:Drop down selection!": ["only", "select", "one"], "slider:slider min,max,step,initial": [0.0, 1.0, 0.01, 0.5]}]
-            }"#;
-
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
+        Plugins::TokenReplay => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let path: &str = params.parameters[0]["file:PetriNet"].as_str().unwrap();
+                share_progress("Importing Object-Centric Petri Net", &mut curr_step, total_steps, &handler);
+                let net: OcPetriNet = match import_pnml(path) {
+                    Ok(net) => net,
+                    Err(e) => {return Err(e);}
+                };
+
+                share_progress("Replaying Objects", &mut curr_step, total_steps, &handler);
+                let (results, summary) = conformance::token_replay(&ocel.object, &net);
+                share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                let df = DataFrame::new(vec![Series::new("Object", results.iter().map(|r| r.object.as_str()).collect::<Vec<&str>>()),
+                                             Series::new("Object Type", results.iter().map(|r| r.object_type.as_str()).collect::<Vec<&str>>()),
+                                             Series::new("Fitness", results.iter().map(|r| r.fitness()).collect::<Vec<f64>>()),
+                                             Series::new("Consumed", results.iter().map(|r| r.consumed).collect::<Vec<u32>>()),
+                                             Series::new("Produced", results.iter().map(|r| r.produced).collect::<Vec<u32>>()),
+                                             Series::new("Missing", results.iter().map(|r| r.missing).collect::<Vec<u32>>()),
+                                             Series::new("Remaining", results.iter().map(|r| r.remaining).collect::<Vec<u32>>()),
+                                             Series::new("Deviating Activities", results.iter().map(|r| r.deviating.join(";")).collect::<Vec<String>>())]).expect("Data Table Creation went wrong");
+
+                let total_objects: usize = summary.values().map(|s| s.objects).sum();
+                let fitness: f64 = summary.values().map(|s| s.fitness * s.objects as f64).sum::<f64>() / total_objects.max(1) as f64;
+                metadata.entry("name".to_string()).or_insert(json!(format!("Token Replay of {}", ocel.metadata["name"].as_str().unwrap())));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                instancedata.entry("petrinet-used".to_string()).or_insert(json!(path));
+                instancedata.entry("Fitness".to_string()).or_insert(json!(fitness));
+                instancedata.entry("Fitness per Object Type".to_string()).or_insert(json!(summary.iter().map(|(ot, s)| (ot.to_string(), s.fitness)).collect::<HashMap<String, f64>>()));
+                instancedata.entry("Precision per Object Type".to_string()).or_insert(json!(summary.iter().map(|(ot, s)| (ot.to_string(), s.precision)).collect::<HashMap<String, f64>>()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "TokenReplay", &[iocel]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+            }
         },
-        Plugins::OcelTimeSeries => {
-        let plug = r#"{
-                "id": 6,
-                "name": "Generate OCEL Timeseries",
-                "total_steps": 2,
-                "enumid": "OcelTimeSeries",
-                "description": "Generate a time series based on the events in an OCEL.",
-                "type": "Generation",
-                "input": {"ocel": 1},
-                "output": {"table": 1},
-                "parameters": [{"header": "General", 
-                                "dropdown:SelectSeriesType": ["ActivityCount", "ObjectCount", "UniqueObjectCount"]}]
-            }"#;
-
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
+        Plugins::ProcessExecutions => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let iocdg: Option<usize> = params.inputs.get("ocdg").and_then(|ids| ids.first()).map(|i| i.parse().unwrap());
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                share_progress("Extracting Process Executions", &mut curr_step, total_steps, &handler);
+                let execs: Vec<executions::Execution> = match params.parameters[0]["dropdown:ConnectedBy"].as_str() {
+                    Some("Ocdg") => {
+                        match iocdg.map(|i| &state[&i]) {
+                            Some(Entity::Ocdg(ocdg)) => {
+                                let net = &ocdg.object.net;
+                                let object_edges: Vec<(usize, usize)> = net.edge_indices().filter_map(|e| {
+                                    let (src, tar) = net.edge_endpoints(e)?;
+                                    let src_name = ocdg.object.object_map.get_by_right(&net[src])?;
+                                    let tar_name = ocdg.object.object_map.get_by_right(&net[tar])?;
+                                    Some((*ocel.object.object_map.get_by_left(src_name)?, *ocel.object.object_map.get_by_left(tar_name)?))
+                                }).collect();
+                                executions::executions_by_object_graph(&ocel.object, &object_edges)
+                            },
+                            _ => {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err("Connecting objects by OCDG requires an ocdg input".to_string());
+                            }
+                        }
+                    },
+                    _ => executions::executions_by_shared_events(&ocel.object)
+                };
+
+                share_progress("Computing Execution Variants", &mut curr_step, total_steps, &handler);
+                let variants: Vec<executions::Variant> = executions::execution_variants(&ocel.object, &execs);
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let mut execution_variant: Vec<u32> = vec![0; execs.len()];
+                variants.iter().enumerate().for_each(|(v, variant)| variant.executions.iter().for_each(|ex| execution_variant[*ex] = v as u32 + 1));
+
+                let df = DataFrame::new(vec![Series::new("Variant", (1..=variants.len() as u32).collect::<Vec<u32>>()),
+                                             Series::new("Frequency", variants.iter().map(|v| v.executions.len() as u32).collect::<Vec<u32>>()),
+                                             Series::new("Events", variants.iter().map(|v| v.events as u32).collect::<Vec<u32>>()),
+                                             Series::new("Objects", variants.iter().map(|v| v.objects as u32).collect::<Vec<u32>>()),
+                                             Series::new("Example Execution", variants.iter().map(|v| v.executions[0] as u32 + 1).collect::<Vec<u32>>()),
+                                             Series::new("Approximate", variants.iter().map(|v| v.approximate).collect::<Vec<bool>>())]).expect("Data Table Creation went wrong");
+
+                let mut obj_names: Vec<&str> = vec![];
+                let mut obj_execution: Vec<u32> = vec![];
+                let mut obj_variant: Vec<u32> = vec![];
+                execs.iter().enumerate().for_each(|(i, ex)| ex.objects.iter().for_each(|oid| {
+                    obj_names.push(ocel.object.object_map.get_by_right(oid).unwrap().as_str());
+                    obj_execution.push(i as u32 + 1);
+                    obj_variant.push(execution_variant[i]);
+                }));
+                let object_df = DataFrame::new(vec![Series::new("Object", obj_names), Series::new("Execution", obj_execution), Series::new("Variant", obj_variant)]).expect("Data Table Creation went wrong");
+
+                let ocel_name = ocel.metadata["name"].as_str().unwrap().to_string();
+                let attributes = ocel.attributes.clone();
+                let sources: Vec<usize> = std::iter::once(iocel).chain(iocdg).collect();
+                let materialise = params.parameters[0]["number:MaterialiseVariant"].as_f64().unwrap_or(0.0) as usize;
+                let variant_ocel: Option<Ocel> = if materialise > 0 && materialise <= variants.len() {
+                    let variant_execs = variants[materialise - 1].executions.iter().map(|ex| &execs[*ex]);
+                    let (events, objects): (HashSet<usize>, HashSet<usize>) = variant_execs.fold((HashSet::new(), HashSet::new()), |(mut evs, mut objs), ex| {
+                        evs.extend(ex.events.iter().copied());
+                        objs.extend(ex.objects.iter().copied());
+                        (evs, objs)
+                    });
+                    Some(filtering::extract_sublog(&ocel.object, &events, &objects))
+                } else {
+                    None
+                };
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("Execution Variants of {:?}", ocel_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel_name));
+                instancedata.entry("Execution #".to_string()).or_insert(json!(execs.len()));
+                instancedata.entry("Variant #".to_string()).or_insert(json!(variants.len()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "ProcessExecutions", &sources);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let used = Map::from_iter([("ocel-used".to_string(), json!(ocel_name))]);
+                output_ids.push(store_table(format!("Process Executions of {:?}", ocel_name), object_df, used.clone(), "ProcessExecutions", &sources, &mut state));
+
+                if let Some(variant_ocel) = variant_ocel {
+                    output_ids.push(store_ocel(format!("Variant {} of {}", materialise, ocel_name), variant_ocel, used, attributes, &mut state));
+                }
+            }
         },
-        Plugins::OcelObjectSituations => {
-        let plug = r#"{
-                "id": 7,
-                "name": "Object Situation Targets",
-                "total_steps": 2,
-                "enumid": "OcelObjectSituations",
-                "description": "Gather all object targets that contains a specific set of requirements.",
-                "type": "Generation",
-                "input": {"ocel": 1},
-                "output": {"table": 1},
-                "parameters": [{"header": "General", 
-                                "dropdown:SelectSituationType": ["ObjectAttribute", "ObjectAttributeUnknown", 
-                                                                 "ObjectMissingActivity", "ObjectLifetime", 
-                                                                 "ObjectMissingReachableObjectType"],
-                                "string:Activities": "",
-                                "string:Property": "",
-                                "string:ObjectTypes": ""}]
-            }"#;
+        Plugins::ObjectPointFeatures => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let iocdg: usize = params.inputs[&"ocdg".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                if let Entity::Ocdg(ocdg) = &state[&iocdg] {
+                    let mut selection = features::ObjectPointSelection::default();
+                    if let Some(chosen) = params.parameters[0]["multichoice:Features"].as_array() {
+                        selection.features = chosen.iter().filter_map(|f| f.as_str()).collect();
+                    }
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
+                    if let Some(pairs) = params.parameters[0]["string:ActivityPairs"].as_str() {
+                        match features::parse_activity_pairs(pairs) {
+                            Ok(p) => {selection.activity_pairs = p;},
+                            Err(e) => {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err(e);
+                            }
+                        }
+                    }
 
-        },
-        Plugins::OcelEventSituations => {
-        let plug = r#"{
-                "id": 8,
-                "name": "Event Situation Targets",
-                "total_steps": 2,
-                "enumid": "OcelEventSituations",
-                "description": "Gather all event targets that contains a specific set of requirements.",
-                "type": "Generation",
-                "input": {"ocel": 1},
-                "output": {"table": 1},
-                "parameters": [{"header": "General", 
-                                "dropdown:SelectSituationType": ["EventChoice", "EventAttribute", 
-                                                                 "EventAttributeUnknown", "EventWait", 
-                                                                 "EventDuration", "EventObjectChoice",
-                                                                 "EventMissingRelation", "EventMissingObjectType"],
-                                "string:Activities": "",
-                                "string:Property": "",
-                                "string:ObjectTypes": "",
-                                "string:Relations": ""}]
-            }"#;
+                    selection.object_types = statistics::get_object_types(&ocel.object);
+                    if let Some(object_types) = params.parameters[0]["string:ObjectTypes"].as_str() {
+                        if !object_types.is_empty() {
+                            selection.object_types = object_types.split(";").map(|ot| ot.to_string()).collect();
+                        }
+                    }
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
-            return Some(val_ocel);
-        
-        },
-        Plugins::AllObjectGroupFeatures => {
-        let plug = r#"{
-                "id": 9,
-                "name": "Generate all Object Group Features",
-                "total_steps": 2,
-                "enumid": "AllObjectGroupFeatures",
-                "description": "Generate all global object features based on default values. This plugin only returns features based on objects that are in both the ocel and ocdg.",
-                "type": "Feature Extraction",
-                "input": {"ocel": 1, "ocdg": 1},
-                "output": {"table": 1},
-                "parameters": []
-            }"#;
+                    selection.relations = Relations::iter().collect();
+                    if let Some(relations) = params.parameters[0]["string:Relations"].as_str() {
+                        if !relations.is_empty() {
+                            match relations.split(";").map(Relations::from_str).collect::<Result<Vec<Relations>, _>>() {
+                                Ok(rels) => {selection.relations = rels;},
+                                Err(_) => {
+                                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                    return Err("Invalid Input Relations".to_string());
+                                }
+                            }
+                        }
+                    }
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+                    let params: Vec<(ObjectPoint, Option<Value>)> = match features::selected_object_point_params(&selection) {
+                        Ok(p) => p,
+                        Err(e) => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err(e);
+                        }
+                    };
+
+                    share_progress("Extracting Object Point Features", &mut curr_step, total_steps, &handler);
+                    let (df, columns) = provenance::build_features(params, "ObjectPoint", &[iocel, iocdg], |part| object_point_features(ObjectPointConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: part }))?;
+                    share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                    metadata.entry("name".to_string()).or_insert(json!(format!("Object Point Features {:?}", &id)));
+                    metadata.entry("type".to_string()).or_insert(json!("table"));
+                    metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                    instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                    instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
+                    instancedata.entry("Features".to_string()).or_insert(json!(selection.features));
+
+                    instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                    state.entry(id).or_insert(Entity::Table(new_table));
+                }
+            }
+        },
+        Plugins::AttributeOperatorFeatures => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let operators: Vec<String> = match params.parameters[0]["multichoice:Operators"].as_array() {
+                    Some(ops) if !ops.is_empty() => ops.iter().filter_map(|op| op.as_str().map(|o| o.to_string())).collect(),
+                    _ => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("Invalid Input Operators".to_string());
+                    }
+                };
+
+                let mut attributes: Vec<String> = match ocel.object.global_log.get("ocel:attribute-names") {
+                    Some(Value::Array(attrs)) => attrs.iter().filter_map(|a| a.as_str().map(|s| s.to_string())).collect(),
+                    _ => vec![]
+                };
+                if let Some(input_attrs) = params.parameters[0]["string:Attributes"].as_str() {
+                    if !input_attrs.is_empty() {
+                        attributes = input_attrs.split(";").map(|a| a.to_string()).collect();
+                    }
+                }
+                let per_activity = params.parameters[0]["bool:PerActivity"].as_bool().unwrap_or(false);
+
+                share_progress("Extracting Attribute Operator Features", &mut curr_step, total_steps, &handler);
+                let result = features::attribute_operator_features(&ocel.object, &attributes, &operators, per_activity);
+                share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                metadata.entry("name".to_string()).or_insert(json!(format!("Attribute Operator Features {:?}", &id)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                instancedata.entry("Skipped".to_string()).or_insert(json!(result.skipped.iter().map(|(name, reason)| format!("{}: {}", name, reason)).collect::<Vec<String>>()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&result.df)));
+                let mut columns = provenance::table_provenance(&result.df, "AttributeOperator", &[iocel]);
+                columns.iter_mut().skip(1).for_each(|col| {
+                    let parts: Vec<&str> = col.name.split(':').collect();
+                    col.variant = parts.last().map(|op| op.to_string());
+                    col.params = match parts.len() {
+                        3 => Some(json!({"attribute": parts[0], "activity": parts[1], "operator": parts[2]})),
+                        _ => Some(json!({"attribute": parts[0], "operator": parts[parts.len() - 1]}))
+                    };
+                });
+                let new_table = TableEntity {id, object: result.df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+            }
+        },
+        Plugins::EventGroupFeatures => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let iocdg: usize = params.inputs[&"ocdg".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                if let Entity::Ocdg(ocdg) = &state[&iocdg] {
+                    let chosen: Vec<&str> = match params.parameters[0]["multichoice:Features"].as_array() {
+                        Some(f) => f.iter().filter_map(|v| v.as_str()).collect(),
+                        None => vec![]
+                    };
+                    let operators: Vec<String> = match params.parameters[0]["multichoice:Operators"].as_array() {
+                        Some(ops) => ops.iter().filter_map(|op| op.as_str().map(|o| o.to_string())).collect(),
+                        None => vec![]
+                    };
+                    let mut activities: Vec<&str> = ocel.object.activities.iter().map(|act| act.as_str()).collect();
+                    if let Some(input_act) = params.parameters[0]["string:Activities"].as_str() {
+                        if !input_act.is_empty() {
+                            activities = input_act.split(";").collect();
+                        }
+                    }
+
+                    let feature_params: Vec<(EventGroup, Option<Value>)> = match features::selected_event_group_params(&chosen, &operators, &activities) {
+                        Ok(p) => p,
+                        Err(e) => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err(e);
+                        }
+                    };
+
+                    let df: DataFrame;
+                    let columns: Vec<ColumnProvenance>;
+                    let dry_run = params.parameters[0]["bool:DryRun"].as_bool().unwrap_or(false);
+                    if dry_run {
+                        share_progress("Counting Event Group Features", &mut curr_step, total_steps, &handler);
+                        let counts = features::count_event_group_columns(&ocel.object, &chosen, &operators, &activities);
+                        df = DataFrame::new(vec![Series::new("Feature", counts.iter().map(|(f, _)| *f).collect::<Vec<&str>>()),
+                                                 Series::new("Columns", counts.iter().map(|(_, c)| *c as u32).collect::<Vec<u32>>())]).expect("Data Table Creation went wrong");
+                        metadata.entry("name".to_string()).or_insert(json!(format!("Event Group Features Dry Run {:?}", &id)));
+                        instancedata.entry("Parameter Combinations".to_string()).or_insert(json!(feature_params.len()));
+                        instancedata.entry("Columns".to_string()).or_insert(json!(counts.iter().map(|(_, c)| c).sum::<usize>()));
+                        columns = provenance::table_provenance(&df, "EventGroupFeatures", &[iocel, iocdg]);
+                    } else {
+                        share_progress("Extracting Event Group Features", &mut curr_step, total_steps, &handler);
+                        let (features_df, feature_columns) = provenance::build_features(feature_params, "EventGroup", &[iocel, iocdg], |part| event_group_features(EventGroupConfig { ocel: &ocel.object, ocdg: &ocdg.object, params: part }))?;
+                        df = features_df;
+                        columns = feature_columns;
+                        metadata.entry("name".to_string()).or_insert(json!(format!("Event Group Features {:?}", &id)));
+                    }
+
+                    share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                    metadata.entry("type".to_string()).or_insert(json!("table"));
+                    metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                    instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel.metadata["name"]));
+                    instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
+
+                    instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                    state.entry(id).or_insert(Entity::Table(new_table));
+                }
+            }
+        },
+        Plugins::SituationTable => {
+            let itables: Vec<usize> = params.inputs[&"table".to_string()].iter().map(|i| i.parse().unwrap()).collect();
+            let mut state = entitystate.0.lock().unwrap();
+            let inputs: Vec<tables::TableInput> = itables.iter().filter_map(|i| match &state[i] {
+                Entity::Table(table) => Some(tables::TableInput { id: *i, df: &table.object, columns: &table.columns }),
+                _ => None
+            }).collect();
+
+            // the target table either contains the given column or is the only situation table
+            let target_column = params.parameters[0]["string:TargetColumn"].as_str().unwrap_or("");
+            let candidates: Vec<usize> = (0..inputs.len()).filter(|t| {
+                if target_column.is_empty() {
+                    inputs[*t].columns.iter().any(|col| col.family == "ObjectSituations" || col.family == "EventSituations")
+                } else {
+                    inputs[*t].df.get_column_names().iter().skip(1).any(|name| *name == target_column)
+                }
+            }).collect();
+            if candidates.len() != 1 {
+                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                return Err(format!("Expected exactly one situation target table, found {}", candidates.len()));
+            }
+
+            share_progress("Joining Target and Feature Tables", &mut curr_step, total_steps, &handler);
+            let target = &inputs[candidates[0]];
+            let feature_tables: Vec<tables::TableInput> = inputs.iter().enumerate()
+                                                                .filter(|(t, _)| *t != candidates[0])
+                                                                .map(|(_, t)| tables::TableInput { id: t.id, df: t.df, columns: t.columns })
+                                                                .collect();
+            let drop_missing = params.parameters[0]["bool:DropMissingTargets"].as_bool().unwrap_or(false);
+            let result = tables::build_situation_table(target, &feature_tables, drop_missing)?;
+
+            share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+            let target_id = target.id;
+            metadata.entry("name".to_string()).or_insert(json!(format!("Situation Table {:?}", &id)));
+            metadata.entry("type".to_string()).or_insert(json!("table"));
+            metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+            instancedata.entry("target-table".to_string()).or_insert(json!(target_id));
+            instancedata.entry("feature-tables".to_string()).or_insert(json!(feature_tables.iter().map(|t| t.id).collect::<Vec<usize>>()));
+            instancedata.entry("Dropped Rows".to_string()).or_insert(json!(result.dropped));
+            instancedata.entry("Rows without all Features".to_string()).or_insert(json!(result.unmatched));
+            instancedata.entry("Rows without Id".to_string()).or_insert(json!(result.missing_ids));
+            instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&result.df)));
+            let new_table = TableEntity {id, object: result.df, metadata, instancedata, columns: result.columns};
+            state.entry(id).or_insert(Entity::Table(new_table));
+        },
+        Plugins::DecisionTree => {
+            let itable: usize = params.inputs[&"table".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Table(table) = &state[&itable] {
+                let target = params.parameters[0]["string:TargetColumn"].as_str().unwrap_or("");
+                if target.is_empty() {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err("Invalid Input Target Column".to_string());
+                }
+                let feature_columns: Vec<&str> = match params.parameters[0]["string:FeatureColumns"].as_str() {
+                    Some(cols) if !cols.is_empty() => cols.split(";").collect(),
+                    _ => vec![]
+                };
+                let task: Option<tree::Task> = params.parameters[0]["dropdown:Task"].as_str().and_then(|t| tree::Task::from_str(t).ok());
+                let config = tree::TreeConfig {
+                    max_depth: params.parameters[0]["number:MaxDepth"].as_f64().unwrap_or(5.0).max(1.0) as usize,
+                    min_samples_leaf: params.parameters[0]["number:MinSamplesLeaf"].as_f64().unwrap_or(5.0).max(1.0) as usize
+                };
+                let test_fraction = params.parameters[0]["slider:TestFraction"].as_f64().unwrap_or(0.0).clamp(0.0, 0.9);
+                let seed = params.parameters[0]["number:Seed"].as_f64().unwrap_or(42.0) as u64;
+
+                let data = match tree::training_data(&table.object, target, &feature_columns, task) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+
+                share_progress("Training Decision Tree", &mut curr_step, total_steps, &handler);
+                let mut rows: Vec<usize> = (0..data.y.len()).collect();
+                rng::Rng::new(seed).shuffle(&mut rows);
+                let test_rows: Vec<usize> = rows.split_off(rows.len() - (rows.len() as f64 * test_fraction) as usize);
+                let model = tree::fit_tree(&data, &rows, &config);
+
+                share_progress("Evaluating Decision Tree", &mut curr_step, total_steps, &handler);
+                let mut metrics: Map<String, Value> = Map::new();
+                tree::evaluate(&model, &data, &rows).into_iter().for_each(|(name, value)| {metrics.insert(format!("train {}", name), json!(value));});
+                tree::evaluate(&model, &data, &test_rows).into_iter().for_each(|(name, value)| {metrics.insert(format!("test {}", name), json!(value));});
+
+                let df = DataFrame::new(vec![Series::new("Feature", model.features.clone()),
+                                             Series::new("Importance", model.importances.clone())]).expect("Data Table Creation went wrong");
+                metadata.entry("name".to_string()).or_insert(json!(format!("Feature Importances for {}", target)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("table-used".to_string()).or_insert(json!(table.metadata["name"]));
+                instancedata.entry("Task".to_string()).or_insert(json!(format!("{:?}", model.task)));
+                instancedata.entry("Training Rows".to_string()).or_insert(json!(rows.len()));
+                instancedata.entry("Test Rows".to_string()).or_insert(json!(test_rows.len()));
+                instancedata.entry("Depth".to_string()).or_insert(json!(model.depth()));
+                instancedata.entry("Leaves".to_string()).or_insert(json!(model.leaves()));
+                instancedata.entry("Metrics".to_string()).or_insert(Value::Object(metrics));
+                let model_instancedata = instancedata.clone();
+                let table_name = table.metadata["name"].as_str().unwrap_or_default().to_string();
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "DecisionTree", &[itable]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                output_ids.push(store_model(format!("Decision Tree for {} of {}", target, table_name), Model::DecisionTree(model), model_instancedata, &mut state));
+            }
+        },
+        Plugins::ApplyModel => {
+            let imodel: usize = params.inputs[&"model".to_string()][0].parse().unwrap();
+            let itable: usize = params.inputs[&"table".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Model(model) = &state[&imodel] {
+                if let Entity::Table(table) = &state[&itable] {
+                    let column_name = match params.parameters[0]["string:PredictionColumn"].as_str() {
+                        Some(name) if !name.is_empty() => name.to_string(),
+                        _ => format!("{} prediction", model.object.target().unwrap_or("model"))
+                    };
+                    if table.object.get_column_names().contains(&column_name.as_str()) {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(format!("The table already has a column {}", column_name));
+                    }
+
+                    share_progress("Applying Model", &mut curr_step, total_steps, &handler);
+                    let predictions: Series = model.object.predict(&table.object, &column_name)?;
+                    let mut df = table.object.clone();
+                    let mut columns = table.columns.clone();
+                    columns.push(provenance::ColumnProvenance {
+                        name: column_name.to_string(),
+                        family: "ApplyModel".to_string(),
+                        variant: Some(model.object.kind().to_string()),
+                        params: None,
+                        sources: vec![imodel, itable],
+                        dtype: predictions.dtype().to_string()
+                    });
+                    df.with_column(predictions).map_err(|e| e.to_string())?;
+
+                    share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                    metadata.entry("name".to_string()).or_insert(json!(format!("{} scored by {}", table.metadata["name"].as_str().unwrap_or_default(), model.metadata["name"].as_str().unwrap_or_default())));
+                    metadata.entry("type".to_string()).or_insert(json!("table"));
+                    metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                    instancedata.entry("table-used".to_string()).or_insert(json!(table.metadata["name"]));
+                    instancedata.entry("model-used".to_string()).or_insert(json!(model.metadata["name"]));
+                    instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                    let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                    state.entry(id).or_insert(Entity::Table(new_table));
+                }
+            }
+        },
+        Plugins::Clustering => {
+            let itable: usize = params.inputs[&"table".to_string()][0].parse().unwrap();
+            let iocel: Option<usize> = params.inputs.get("ocel").and_then(|ids| ids.first()).map(|i| i.parse().unwrap());
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Table(table) = &state[&itable] {
+                let requested: Vec<&str> = match params.parameters[0]["string:Columns"].as_str() {
+                    Some(cols) if !cols.is_empty() => cols.split(";").collect(),
+                    _ => vec![]
+                };
+                let (names, raw) = match tables::numeric_columns(&table.object, &requested, &[]) {
+                    Ok(cols) => cols,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                let mut scaled = raw.clone();
+                if params.parameters[0]["bool:Standardise"].as_bool().unwrap_or(false) {
+                    clustering::standardise(&mut scaled);
+                }
+                let (rows, points) = clustering::complete_rows(&scaled);
+
+                share_progress("Clustering Rows", &mut curr_step, total_steps, &handler);
+                let algorithm = params.parameters[0]["dropdown:Algorithm"].as_str().unwrap_or("KMeans");
+                let point_labels: Vec<i64> = match algorithm {
+                    "DBSCAN" => {
+                        let epsilon = params.parameters[0]["number:Epsilon"].as_f64().unwrap_or(0.5);
+                        let min_points = params.parameters[0]["number:MinPoints"].as_f64().unwrap_or(5.0).max(1.0) as usize;
+                        if epsilon <= 0.0 {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err("Invalid Input Epsilon".to_string());
+                        }
+                        clustering::dbscan(&points, epsilon, min_points)
+                    },
+                    _ => {
+                        let k = params.parameters[0]["number:Clusters"].as_f64().unwrap_or(3.0);
+                        if k < 1.0 {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err("Invalid Input Clusters".to_string());
+                        }
+                        let max_iterations = params.parameters[0]["number:MaxIterations"].as_f64().unwrap_or(100.0).max(1.0) as usize;
+                        let seed = params.parameters[0]["number:Seed"].as_f64().unwrap_or(42.0) as u64;
+                        let result = clustering::kmeans(&points, k as usize, max_iterations, seed);
+                        instancedata.entry("Iterations".to_string()).or_insert(json!(result.iterations));
+                        instancedata.entry("Inertia".to_string()).or_insert(json!(result.inertia));
+                        result.labels
+                    }
+                };
+
+                // rows with missing values are not clustered
+                let mut labels: Vec<Option<i64>> = vec![None; table.object.height()];
+                rows.iter().zip(&point_labels).for_each(|(r, l)| labels[*r] = Some(*l));
+                let mut clusters: Vec<i64> = point_labels.clone();
+                clusters.sort_unstable();
+                clusters.dedup();
+
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let ids: Vec<Option<String>> = tables::id_column(&table.object)?;
+                let id_name = table.object.get_column_names()[0].to_string();
+                let df = DataFrame::new(vec![Series::new(id_name.as_str(), ids.clone()),
+                                             Series::new("Cluster", labels.clone())]).expect("Data Table Creation went wrong");
+
+                let mut centroid_series: Vec<Series> = vec![Series::new("Cluster", clusters.clone()),
+                                                            Series::new("Size", clusters.iter().map(|c| point_labels.iter().filter(|l| *l == c).count() as u32).collect::<Vec<u32>>())];
+                for (name, column) in names.iter().zip(&raw) {
+                    centroid_series.push(Series::new(name.as_str(), clusters.iter().map(|c| {
+                        let members: Vec<f64> = rows.iter().zip(&point_labels).filter(|(_, l)| *l == c).map(|(r, _)| column[*r]).collect();
+                        members.iter().sum::<f64>() / members.len().max(1) as f64
+                    }).collect::<Vec<f64>>()));
+                }
+                let centroids = DataFrame::new(centroid_series).expect("Data Table Creation went wrong");
+
+                let table_name = table.metadata["name"].as_str().unwrap_or_default().to_string();
+                let attributes: Vec<ColumnProvenance> = match iocel.map(|i| &state[&i]) {
+                    Some(Entity::Ocel(ocel)) => ocel.attributes.clone(),
+                    _ => vec![]
+                };
+                let sublogs: Vec<(i64, Ocel)> = match (params.parameters[0]["bool:SplitOcel"].as_bool().unwrap_or(false), iocel.map(|i| &state[&i])) {
+                    (true, Some(Entity::Ocel(ocel))) => clusters.iter().filter(|c| **c != clustering::NOISE).map(|c| {
+                        let members: Vec<&str> = ids.iter().zip(&labels).filter(|(_, l)| **l == Some(*c)).filter_map(|(id, _)| id.as_deref()).collect();
+                        (*c, filtering::sublog_from_ids(&ocel.object, &members))
+                    }).collect(),
+                    (true, _) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("Splitting the OCEL requires an ocel input".to_string());
+                    },
+                    _ => vec![]
+                };
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("{} Clusters of {}", algorithm, table_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("table-used".to_string()).or_insert(json!(table_name));
+                instancedata.entry("Algorithm".to_string()).or_insert(json!(algorithm));
+                instancedata.entry("Columns".to_string()).or_insert(json!(names));
+                instancedata.entry("Cluster #".to_string()).or_insert(json!(clusters.iter().filter(|c| **c != clustering::NOISE).count()));
+                instancedata.entry("Noise #".to_string()).or_insert(json!(point_labels.iter().filter(|l| **l == clustering::NOISE).count()));
+                instancedata.entry("Unclustered Rows".to_string()).or_insert(json!(table.object.height() - rows.len()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "Clustering", &[itable]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let used = Map::from_iter([("table-used".to_string(), json!(table_name))]);
+                output_ids.push(store_table(format!("{} Centroids of {}", algorithm, table_name), centroids, used.clone(), "Clustering", &[itable], &mut state));
+
+                for (cluster, sublog) in sublogs {
+                    output_ids.push(store_ocel(format!("Cluster {} of {}", cluster, table_name), sublog, used.clone(), attributes.clone(), &mut state));
+                }
+            }
+        },
+        Plugins::AnomalyDetection => {
+            let itable: usize = params.inputs[&"table".to_string()][0].parse().unwrap();
+            let iocel: Option<usize> = params.inputs.get("ocel").and_then(|ids| ids.first()).map(|i| i.parse().unwrap());
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Table(table) = &state[&itable] {
+                let requested: Vec<&str> = match params.parameters[0]["string:Columns"].as_str() {
+                    Some(cols) if !cols.is_empty() => cols.split(";").collect(),
+                    _ => vec![]
+                };
+                let (names, values) = match tables::numeric_columns(&table.object, &requested, &[]) {
+                    Ok(cols) => cols,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+
+                share_progress("Scoring Rows", &mut curr_step, total_steps, &handler);
+                let method = params.parameters[0]["dropdown:Method"].as_str().unwrap_or("IsolationForest");
+                let (scores, threshold): (Vec<Option<f64>>, f64) = match method {
+                    "ZScore" => (anomaly::z_scores(&values), params.parameters[0]["number:ZThreshold"].as_f64().unwrap_or(3.0)),
+                    "IQR" => (anomaly::iqr_scores(&values), params.parameters[0]["number:IqrFactor"].as_f64().unwrap_or(1.5)),
+                    _ => {
+                        let trees = params.parameters[0]["number:Trees"].as_f64().unwrap_or(100.0).max(1.0) as usize;
+                        let sample_size = params.parameters[0]["number:SampleSize"].as_f64().unwrap_or(256.0).max(2.0) as usize;
+                        let seed = params.parameters[0]["number:Seed"].as_f64().unwrap_or(42.0) as u64;
+                        // rows with missing values are not scored
+                        let (rows, points) = clustering::complete_rows(&values);
+                        let mut scores: Vec<Option<f64>> = vec![None; table.object.height()];
+                        rows.iter().zip(anomaly::isolation_forest(&points, trees, sample_size, seed)).for_each(|(r, score)| scores[*r] = Some(score));
+                        (scores, params.parameters[0]["slider:IsolationThreshold"].as_f64().unwrap_or(0.6))
+                    }
+                };
+                let flags: Vec<Option<bool>> = scores.iter().map(|s| s.map(|s| s > threshold)).collect();
+
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let ids: Vec<Option<String>> = tables::id_column(&table.object)?;
+                let id_name = table.object.get_column_names()[0].to_string();
+                let df = DataFrame::new(vec![Series::new(id_name.as_str(), ids.clone()),
+                                             Series::new("Anomaly Score", scores),
+                                             Series::new("Anomaly", flags.clone())]).expect("Data Table Creation went wrong");
+
+                let flagged: Vec<&str> = ids.iter().zip(&flags).filter(|(_, f)| **f == Some(true)).filter_map(|(id, _)| id.as_deref()).collect();
+                let tag_attribute = match params.parameters[0]["string:TagAttribute"].as_str() {
+                    Some(attr) if !attr.is_empty() => attr.to_string(),
+                    _ => "anomaly".to_string()
+                };
+                let tagged_ocel: Option<(Ocel, String, Vec<ColumnProvenance>)> = match (params.parameters[0]["bool:TagOcel"].as_bool().unwrap_or(false), iocel.map(|i| &state[&i])) {
+                    (true, Some(Entity::Ocel(ocel))) => {
+                        let mut new_ocel = ocel.object.clone();
+                        let flagged_set: HashSet<&str> = flagged.iter().copied().collect();
+                        for (name, oid) in ocel.object.object_map.iter() {
+                            if let Some(obj) = new_ocel.objects.get_mut(oid) {
+                                obj.ovmap.insert(tag_attribute.to_string(), json!(flagged_set.contains(name.as_str())));
+                            }
+                        }
+                        let mut attributes: Vec<ColumnProvenance> = ocel.attributes.iter().filter(|attr| attr.name != tag_attribute).cloned().collect();
+                        attributes.push(ColumnProvenance { name: tag_attribute.to_string(), family: "AnomalyDetection".to_string(), variant: Some(method.to_string()), params: None, sources: vec![itable], dtype: "bool".to_string() });
+                        Some((new_ocel, ocel.metadata["name"].as_str().unwrap_or_default().to_string(), attributes))
+                    },
+                    (true, _) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("Tagging objects requires an ocel input".to_string());
+                    },
+                    _ => None
+                };
+
+                let table_name = table.metadata["name"].as_str().unwrap_or_default().to_string();
+                metadata.entry("name".to_string()).or_insert(json!(format!("{} Anomalies of {}", method, table_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("table-used".to_string()).or_insert(json!(table_name));
+                instancedata.entry("Method".to_string()).or_insert(json!(method));
+                instancedata.entry("Threshold".to_string()).or_insert(json!(threshold));
+                instancedata.entry("Columns".to_string()).or_insert(json!(names));
+                instancedata.entry("Anomaly #".to_string()).or_insert(json!(flagged.len()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "AnomalyDetection", &[itable]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                if let Some((new_ocel, ocel_name, attributes)) = tagged_ocel {
+                    let ocel_instancedata = Map::from_iter([("table-used".to_string(), json!(table_name)), ("Tag Attribute".to_string(), json!(tag_attribute))]);
+                    output_ids.push(store_ocel(format!("{} tagged by {}", ocel_name, method), new_ocel, ocel_instancedata, attributes, &mut state));
+                }
+            }
+        },
+        Plugins::Forecasting => {
+            let itable: usize = params.inputs[&"table".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Table(table) = &state[&itable] {
+                let requested: Vec<&str> = match params.parameters[0]["string:Column"].as_str() {
+                    Some(col) if !col.is_empty() => vec![col],
+                    _ => vec![]
+                };
+                let (column, series): (String, Vec<f64>) = match tables::numeric_columns(&table.object, &requested, &[]) {
+                    Ok((names, values)) if !names.is_empty() => (names[0].to_string(), values[0].clone()),
+                    Ok(_) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("The table has no numeric column".to_string());
+                    },
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                // dropping missing values would shift the seasons and lags of all later values
+                let missing = series.iter().filter(|v| v.is_nan()).count();
+                if missing > 0 {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err(format!("The column {} has {} missing values", column, missing));
+                }
+
+                let method_name = params.parameters[0]["dropdown:Method"].as_str().unwrap_or("SimpleExponentialSmoothing");
+                let config = forecasting::ForecastConfig {
+                    method: match forecasting::Method::from_str(method_name) {
+                        Ok(method) => method,
+                        Err(_) => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err("Invalid Input Method".to_string());
+                        }
+                    },
+                    alpha: params.parameters[0]["number:Alpha"].as_f64().unwrap_or(0.0),
+                    beta: params.parameters[0]["number:Beta"].as_f64().unwrap_or(0.0),
+                    gamma: params.parameters[0]["number:Gamma"].as_f64().unwrap_or(0.0),
+                    season_length: params.parameters[0]["number:SeasonLength"].as_f64().unwrap_or(7.0).max(0.0) as usize,
+                    lags: params.parameters[0]["number:ArOrder"].as_f64().unwrap_or(2.0).max(1.0) as usize
+                };
+                let horizon = params.parameters[0]["number:Horizon"].as_f64().unwrap_or(10.0);
+                if horizon < 1.0 {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err("Invalid Input Horizon".to_string());
+                }
+                // by default the last 20% of the series are held out
+                let holdout = match params.parameters[0]["number:BacktestSize"].as_f64().unwrap_or(0.0) as usize {
+                    0 => (series.len() / 5).max(1),
+                    size => size
+                };
+
+                share_progress("Backtesting Model", &mut curr_step, total_steps, &handler);
+                let metrics = match forecasting::backtest(&series, &config, holdout) {
+                    Ok(metrics) => metrics,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+
+                share_progress("Forecasting Series", &mut curr_step, total_steps, &handler);
+                let result = match forecasting::forecast(&series, &config, horizon as usize) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                let confidence = params.parameters[0]["slider:Confidence"].as_f64().unwrap_or(0.95).clamp(0.5, 0.999);
+                let z = numeric::normal_quantile((1.0 + confidence) / 2.0);
+                // the interval widens with the square root of the steps ahead
+                let margins: Vec<f64> = (1..=result.values.len()).map(|h| z * result.residual_std * (h as f64).sqrt()).collect();
+
+                let df = DataFrame::new(vec![Series::new("Step", (1..=result.values.len() as u32).collect::<Vec<u32>>()),
+                                             Series::new("Forecast", result.values.clone()),
+                                             Series::new("Lower", result.values.iter().zip(&margins).map(|(v, m)| v - m).collect::<Vec<f64>>()),
+                                             Series::new("Upper", result.values.iter().zip(&margins).map(|(v, m)| v + m).collect::<Vec<f64>>())]).expect("Data Table Creation went wrong");
+
+                let table_name = table.metadata["name"].as_str().unwrap_or_default().to_string();
+                metadata.entry("name".to_string()).or_insert(json!(format!("{} Forecast of {}", method_name, column)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("table-used".to_string()).or_insert(json!(table_name));
+                instancedata.entry("Column".to_string()).or_insert(json!(column));
+                instancedata.entry("Method".to_string()).or_insert(json!(method_name));
+                instancedata.entry("Confidence".to_string()).or_insert(json!(confidence));
+                instancedata.entry("Backtest Size".to_string()).or_insert(json!(holdout));
+                for (name, value) in &result.parameters {
+                    instancedata.entry(name.to_string()).or_insert(json!(value));
+                }
+                let used = instancedata.clone();
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "Forecasting", &[itable]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let (names, values): (Vec<String>, Vec<f64>) = metrics.into_iter().unzip();
+                let metrics_df = DataFrame::new(vec![Series::new("Metric", names),
+                                                     Series::new("Value", values)]).expect("Data Table Creation went wrong");
+                output_ids.push(store_table(format!("Backtest of {} Forecast of {}", method_name, column), metrics_df, used, "Forecasting", &[itable], &mut state));
+            }
+        },
+        Plugins::DriftDetection => {
+            let iocel: usize = params.inputs[&"ocel".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocel(ocel) = &state[&iocel] {
+                let signatures: Vec<&str> = match params.parameters[0]["multichoice:Signatures"].as_array() {
+                    Some(sigs) if !sigs.is_empty() => sigs.iter().filter_map(|s| s.as_str()).collect(),
+                    _ => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("Invalid Input Signatures".to_string());
+                    }
+                };
+
+                let mut durations: Vec<Option<Duration>> = vec![];
+                for key in ["string:WindowSize", "string:WindowStep"] {
+                    match params.parameters[0][key].as_str() {
+                        Some(d) if !d.is_empty() => match time_series::parse_duration(d) {
+                            Ok(d) => durations.push(Some(d)),
+                            Err(e) => {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err(e);
+                            }
+                        },
+                        _ => durations.push(None)
+                    }
+                }
+                // without a size, the log is split into WindowCount windows
+                let width = match durations[0] {
+                    Some(width) => width,
+                    None => {
+                        let count = params.parameters[0]["number:WindowCount"].as_f64().unwrap_or(10.0).max(2.0);
+                        let span = match (ocel.object.events.values().map(|ev| ev.timestamp).min(), ocel.object.events.values().map(|ev| ev.timestamp).max()) {
+                            (Some(first), Some(last)) => (last - first).num_milliseconds(),
+                            _ => 0
+                        };
+                        Duration::milliseconds(((span + 1) as f64 / count).ceil() as i64).max(Duration::milliseconds(1))
+                    }
+                };
+                let step = durations[1].unwrap_or(width);
+                let significance = params.parameters[0]["slider:Significance"].as_f64().unwrap_or(0.01);
+                let min_distance = params.parameters[0]["slider:MinDistance"].as_f64().unwrap_or(0.1);
+
+                share_progress("Computing Window Signatures", &mut curr_step, total_steps, &handler);
+                let windows = match drift::windows(&ocel.object, width, step) {
+                    Ok(windows) => windows,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                let window_signatures: Vec<Vec<BTreeMap<String, f64>>> = windows.iter().map(|w| signatures.iter().map(|sig| drift::signature(&ocel.object, &w.events, sig)).collect()).collect();
+
+                share_progress("Testing for Change Points", &mut curr_step, total_steps, &handler);
+                // every window is compared to the last window that does not overlap with it
+                let offset = ((width.num_milliseconds() as f64) / (step.num_milliseconds().max(1) as f64)).ceil().max(1.0) as usize;
+                let corrected = significance / signatures.len() as f64;
+                let mut references: Vec<Option<u32>> = vec![];
+                let mut distances: Vec<Vec<Option<f64>>> = vec![vec![]; signatures.len()];
+                let mut p_values: Vec<Vec<Option<f64>>> = vec![vec![]; signatures.len()];
+                let mut drifts: Vec<bool> = vec![];
+                let mut drift_points: Vec<(u32, String, Vec<&str>)> = vec![];
+                for (w, window) in windows.iter().enumerate() {
+                    let reference = w.checked_sub(offset);
+                    references.push(reference.map(|r| r as u32));
+                    let mut changed: Vec<&str> = vec![];
+                    for (s, sig) in signatures.iter().enumerate() {
+                        let comparison = reference.and_then(|r| drift::compare(&window_signatures[r][s], &window_signatures[w][s]));
+                        if let Some(c) = &comparison {
+                            if c.p_value < corrected && c.distance >= min_distance {
+                                changed.push(*sig);
+                            }
+                        }
+                        distances[s].push(comparison.as_ref().map(|c| c.distance));
+                        p_values[s].push(comparison.as_ref().map(|c| c.p_value));
+                    }
+                    drifts.push(!changed.is_empty());
+                    if !changed.is_empty() {
+                        drift_points.push((w as u32, window.start.format("%Y-%m-%d %H:%M:%S").to_string(), changed));
+                    }
+                }
+
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let mut series: Vec<Series> = vec![Series::new("Window", (0..windows.len() as u32).collect::<Vec<u32>>()),
+                                                   Series::new("Start", windows.iter().map(|w| w.start.format("%Y-%m-%d %H:%M:%S").to_string()).collect::<Vec<String>>()),
+                                                   Series::new("End", windows.iter().map(|w| w.end.format("%Y-%m-%d %H:%M:%S").to_string()).collect::<Vec<String>>()),
+                                                   Series::new("Events", windows.iter().map(|w| w.events.len() as u32).collect::<Vec<u32>>()),
+                                                   Series::new("Reference", references)];
+                for (s, sig) in signatures.iter().enumerate() {
+                    series.push(Series::new(format!("{} Distance", sig).as_str(), distances[s].clone()));
+                    series.push(Series::new(format!("{} P-Value", sig).as_str(), p_values[s].clone()));
+                }
+                series.push(Series::new("Drift", drifts));
+                let df = DataFrame::new(series).expect("Data Table Creation went wrong");
+                let points = DataFrame::new(vec![Series::new("Window", drift_points.iter().map(|(w, _, _)| *w).collect::<Vec<u32>>()),
+                                                 Series::new("Timestamp", drift_points.iter().map(|(_, t, _)| t.to_string()).collect::<Vec<String>>()),
+                                                 Series::new("Signatures", drift_points.iter().map(|(_, _, sigs)| sigs.join(";")).collect::<Vec<String>>())]).expect("Data Table Creation went wrong");
+
+                let ocel_name = ocel.metadata["name"].as_str().unwrap_or_default().to_string();
+                let attributes = ocel.attributes.clone();
+                let sublogs: Vec<Ocel> = if params.parameters[0]["bool:SplitOcel"].as_bool().unwrap_or(false) {
+                    windows.iter().map(|w| {
+                        let events: HashSet<usize> = w.events.iter().copied().collect();
+                        let objects: HashSet<usize> = w.events.iter().flat_map(|eid| ocel.object.events[eid].omap.iter().copied()).collect();
+                        filtering::extract_sublog(&ocel.object, &events, &objects)
+                    }).collect()
+                } else {
+                    vec![]
+                };
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("Drift Windows of {}", ocel_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocel-used".to_string()).or_insert(json!(ocel_name));
+                instancedata.entry("Window Size (s)".to_string()).or_insert(json!(width.num_milliseconds() as f64 / 1000.0));
+                instancedata.entry("Window Step (s)".to_string()).or_insert(json!(step.num_milliseconds() as f64 / 1000.0));
+                instancedata.entry("Window #".to_string()).or_insert(json!(windows.len()));
+                instancedata.entry("Drift #".to_string()).or_insert(json!(drift_points.len()));
+                instancedata.entry("Significance".to_string()).or_insert(json!(significance));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "DriftDetection", &[iocel]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let used = Map::from_iter([("ocel-used".to_string(), json!(ocel_name))]);
+                output_ids.push(store_table(format!("Drift Points of {}", ocel_name), points, used.clone(), "DriftDetection", &[iocel], &mut state));
+
+                for (w, sublog) in sublogs.into_iter().enumerate() {
+                    output_ids.push(store_ocel(format!("Window {} of {}", w, ocel_name), sublog, used.clone(), attributes.clone(), &mut state));
+                }
+            }
+        },
+        Plugins::Causality => {
+            let itable: usize = params.inputs[&"table".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Table(table) = &state[&itable] {
+                let requested: Vec<&str> = match params.parameters[0]["string:Columns"].as_str() {
+                    Some(cols) if !cols.is_empty() => cols.split(";").collect(),
+                    _ => vec![]
+                };
+                let (names, mut values) = match tables::numeric_columns(&table.object, &requested, &[]) {
+                    Ok(cols) if cols.0.len() >= 2 => cols,
+                    Ok(_) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err("At least two numeric columns are needed".to_string());
+                    },
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                let max_lag = params.parameters[0]["number:MaxLag"].as_f64().unwrap_or(3.0);
+                if max_lag < 1.0 {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err("Invalid Input Max Lag".to_string());
+                }
+                let max_lag = max_lag as usize;
+                let significance = params.parameters[0]["slider:Significance"].as_f64().unwrap_or(0.05);
+                let differenced = params.parameters[0]["bool:Difference"].as_bool().unwrap_or(false);
+                if differenced {
+                    values = values.iter().map(|v| causality::difference(v)).collect();
+                }
+
+                share_progress("Testing Series Pairs", &mut curr_step, total_steps, &handler);
+                let pairs: Vec<(usize, usize)> = (0..names.len()).flat_map(|c| (0..names.len()).filter(move |e| *e != c).map(move |e| (c, e))).collect();
+                let results: Vec<Option<causality::PairResult>> = pairs.par_iter().map(|(c, e)| causality::analyse_pair(&values[*c], &values[*e], max_lag)).collect();
+
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let tests = causality::test_count(names.len(), max_lag);
+                let adjusted: Vec<Option<f64>> = results.iter().map(|r| r.as_ref().map(|r| causality::bonferroni(r.p_value, tests))).collect();
+                let causal: Vec<Option<bool>> = adjusted.iter().map(|p| p.map(|p| p < significance)).collect();
+                let df = DataFrame::new(vec![Series::new("Cause", pairs.iter().map(|(c, _)| names[*c].to_string()).collect::<Vec<String>>()),
+                                             Series::new("Effect", pairs.iter().map(|(_, e)| names[*e].to_string()).collect::<Vec<String>>()),
+                                             Series::new("Correlation Lag", results.iter().map(|r| r.as_ref().map(|r| r.correlation_lag as u32)).collect::<Vec<Option<u32>>>()),
+                                             Series::new("Correlation", results.iter().map(|r| r.as_ref().map(|r| r.correlation)).collect::<Vec<Option<f64>>>()),
+                                             Series::new("Granger Lag", results.iter().map(|r| r.as_ref().map(|r| r.granger_lag as u32)).collect::<Vec<Option<u32>>>()),
+                                             Series::new("F", results.iter().map(|r| r.as_ref().map(|r| r.f_statistic)).collect::<Vec<Option<f64>>>()),
+                                             Series::new("P-Value", results.iter().map(|r| r.as_ref().map(|r| r.p_value)).collect::<Vec<Option<f64>>>()),
+                                             Series::new("Adjusted P-Value", adjusted.clone()),
+                                             Series::new("Causal", causal)]).expect("Data Table Creation went wrong");
+
+                let edges: Vec<graph::GraphEdge> = pairs.iter().zip(results.iter().zip(&adjusted)).filter_map(|((c, e), (r, p))| match (r, p) {
+                    (Some(r), Some(p)) if *p < significance => Some(graph::GraphEdge {
+                        source: names[*c].to_string(),
+                        target: names[*e].to_string(),
+                        label: format!("lag {} (p={:.3})", r.granger_lag, p),
+                        weight: r.f_statistic,
+                        attributes: BTreeMap::from([("lag".to_string(), json!(r.granger_lag)),
+                                                    ("p-value".to_string(), json!(r.p_value)),
+                                                    ("adjusted p-value".to_string(), json!(p)),
+                                                    ("correlation".to_string(), json!(r.correlation))])
+                    }),
+                    _ => None
+                }).collect();
+                let causal_graph = Graph { directed: true, nodes: names.clone(), edges, node_attributes: BTreeMap::new() };
+
+                let table_name = table.metadata["name"].as_str().unwrap_or_default().to_string();
+                metadata.entry("name".to_string()).or_insert(json!(format!("Causality of {}", table_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("table-used".to_string()).or_insert(json!(table_name));
+                instancedata.entry("Columns".to_string()).or_insert(json!(names));
+                instancedata.entry("Max Lag".to_string()).or_insert(json!(max_lag));
+                instancedata.entry("Significance".to_string()).or_insert(json!(significance));
+                instancedata.entry("Differenced".to_string()).or_insert(json!(differenced));
+                instancedata.entry("Correction".to_string()).or_insert(json!(format!("Bonferroni over {} tests", tests)));
+                instancedata.entry("Causal Pair #".to_string()).or_insert(json!(causal_graph.edges.len()));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "Causality", &[itable]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let used = Map::from_iter([("table-used".to_string(), json!(table_name)), ("Significance".to_string(), json!(significance))]);
+                output_ids.push(store_graph(format!("Causal Graph of {}", table_name), causal_graph, used, &mut state));
+            }
+        },
+        Plugins::OcdgCentrality => {
+            let iocdg: usize = params.inputs[&"ocdg".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocdg(ocdg) = &state[&iocdg] {
+                let mut relations: Vec<String> = Relations::iter().map(|rel| format!("{:?}", rel)).collect();
+                if let Some(rels) = params.parameters[0]["string:Relations"].as_str() {
+                    if !rels.is_empty() {
+                        match rels.split(";").map(Relations::from_str).collect::<Result<Vec<Relations>, _>>() {
+                            Ok(rels) => {relations = rels.iter().map(|rel| format!("{:?}", rel)).collect();},
+                            Err(_) => {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err("Invalid Input Relations".to_string());
+                            }
+                        }
+                    }
+                }
+                let damping = params.parameters[0]["slider:Damping"].as_f64().unwrap_or(0.85);
+                let max_iterations = params.parameters[0]["number:MaxIterations"].as_f64().unwrap_or(100.0).max(1.0) as usize;
+                let skip_betweenness = params.parameters[0]["bool:SkipBetweenness"].as_bool().unwrap_or(false);
+
+                share_progress("Computing Centralities", &mut curr_step, total_steps, &handler);
+                let graph = object_graph::ObjectGraph::from_ocdg(&ocdg.object);
+                let adjacency = graph.undirected(&HashMap::new());
+                let (degree, relation_degrees) = centrality::degrees(&graph, &relations);
+                let mut series: Vec<Series> = vec![Series::new("Object", graph.objects.clone()),
+                                                   Series::new("Degree", degree)];
+                relations.iter().zip(relation_degrees).for_each(|(rel, values)| series.push(Series::new(format!("Degree {}", rel).as_str(), values)));
+                series.push(Series::new("PageRank", centrality::pagerank(&graph, damping, max_iterations)));
+                if !skip_betweenness {
+                    series.push(Series::new("Betweenness", centrality::betweenness(&adjacency)));
+                }
+                series.push(Series::new("Clustering Coefficient", centrality::clustering_coefficients(&adjacency)));
+                let component = centrality::components(&adjacency);
+                let component_count = component.iter().max().map_or(0, |c| c + 1);
+                series.push(Series::new("Component", component));
+
+                share_progress("Storing Result as DataFrame", &mut curr_step, total_steps, &handler);
+                let df = DataFrame::new(series).expect("Data Table Creation went wrong");
+                let mut columns = provenance::table_provenance(&df, "OcdgCentrality", &[iocdg]);
+                columns.iter_mut().filter(|col| col.name.starts_with("Degree ")).for_each(|col| {
+                    col.variant = Some("Degree".to_string());
+                    col.params = Some(json!({"relations": col.name.trim_start_matches("Degree ")}));
+                });
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("Centralities of {}", ocdg.metadata["name"].as_str().unwrap_or_default())));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
+                instancedata.entry("Damping".to_string()).or_insert(json!(damping));
+                instancedata.entry("Component #".to_string()).or_insert(json!(component_count));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+            }
+        },
+        Plugins::OcdgCommunities => {
+            let iocdg: usize = params.inputs[&"ocdg".to_string()][0].parse().unwrap();
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocdg(ocdg) = &state[&iocdg] {
+                let weights = match object_graph::parse_relation_weights(params.parameters[0]["string:RelationWeights"].as_str().unwrap_or_default()) {
+                    Ok(weights) => weights,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                let algorithm = params.parameters[0]["dropdown:Algorithm"].as_str().unwrap_or("Louvain");
+                let resolution = params.parameters[0]["number:Resolution"].as_f64().unwrap_or(1.0);
+                if resolution <= 0.0 {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err("Invalid Input Resolution".to_string());
+                }
+                let max_iterations = params.parameters[0]["number:MaxIterations"].as_f64().unwrap_or(100.0).max(1.0) as usize;
+                let seed = params.parameters[0]["number:Seed"].as_f64().unwrap_or(42.0) as u64;
+
+                share_progress("Detecting Communities", &mut curr_step, total_steps, &handler);
+                let graph = object_graph::ObjectGraph::from_ocdg(&ocdg.object);
+                let adjacency = graph.undirected(&weights);
+                let communities: Vec<usize> = match algorithm {
+                    "LabelPropagation" => community::label_propagation(&adjacency, max_iterations, seed),
+                    _ => community::louvain(&adjacency, resolution, seed)
+                };
+                let modularity = community::modularity(&adjacency, &communities, resolution);
+
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let count = communities.iter().max().map_or(0, |c| c + 1);
+                let object_types: BTreeSet<&str> = graph.object_types.iter().map(|ot| ot.as_str()).collect();
+                let mut composition: BTreeMap<&str, Vec<u32>> = object_types.iter().map(|ot| (*ot, vec![0; count])).collect();
+                let mut sizes: Vec<u32> = vec![0; count];
+                communities.iter().zip(&graph.object_types).for_each(|(c, ot)| {
+                    sizes[*c] += 1;
+                    if let Some(counts) = composition.get_mut(ot.as_str()) {
+                        counts[*c] += 1;
+                    }
+                });
+
+                let df = DataFrame::new(vec![Series::new("Object", graph.objects.clone()),
+                                             Series::new("Community", communities.iter().map(|c| *c as u32).collect::<Vec<u32>>())]).expect("Data Table Creation went wrong");
+                let mut summary_series: Vec<Series> = vec![Series::new("Community", (0..count as u32).collect::<Vec<u32>>()),
+                                                           Series::new("Size", sizes)];
+                composition.into_iter().for_each(|(ot, counts)| summary_series.push(Series::new(format!("{} #", ot).as_str(), counts)));
+                let summary = DataFrame::new(summary_series).expect("Data Table Creation went wrong");
+
+                let ocdg_name = ocdg.metadata["name"].as_str().unwrap_or_default().to_string();
+                metadata.entry("name".to_string()).or_insert(json!(format!("{} Communities of {}", algorithm, ocdg_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg_name));
+                instancedata.entry("Algorithm".to_string()).or_insert(json!(algorithm));
+                instancedata.entry("Relation Weights".to_string()).or_insert(json!(weights));
+                instancedata.entry("Community #".to_string()).or_insert(json!(count));
+                instancedata.entry("Modularity".to_string()).or_insert(json!(modularity));
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "OcdgCommunities", &[iocdg]);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                let used = Map::from_iter([("ocdg-used".to_string(), json!(ocdg_name)), ("Modularity".to_string(), json!(modularity))]);
+                output_ids.push(store_table(format!("{} Community Summary of {}", algorithm, ocdg_name), summary, used, "OcdgCommunities", &[iocdg], &mut state));
+            }
+        },
+        Plugins::OcdgSubgraph => {
+            let iocdg: usize = params.inputs[&"ocdg".to_string()][0].parse().unwrap();
+            let iocel: Option<usize> = params.inputs.get("ocel").and_then(|ids| ids.first()).map(|i| i.parse().unwrap());
+            let mut state = entitystate.0.lock().unwrap();
+            if let Entity::Ocdg(ocdg) = &state[&iocdg] {
+                let mut filter = object_graph::SubgraphFilter::default();
+                if let Some(object_types) = params.parameters[0]["string:ObjectTypes"].as_str() {
+                    if !object_types.is_empty() {
+                        filter.object_types = Some(object_types.split(";").collect());
+                    }
+                }
+                if let Some(relations) = params.parameters[0]["string:Relations"].as_str() {
+                    if !relations.is_empty() {
+                        match relations.split(";").map(Relations::from_str).collect::<Result<HashSet<Relations>, _>>() {
+                            Ok(rels) => {filter.relations = Some(rels);},
+                            Err(_) => {
+                                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                                return Err("Invalid Input Relations".to_string());
+                            }
+                        }
+                    }
+                }
+
+                let (start, end) = match filtering::parse_time_bounds(&params.parameters[0]) {
+                    Ok(bounds) => bounds,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+                if start.is_some() || end.is_some() {
+                    match iocel.map(|i| &state[&i]) {
+                        Some(Entity::Ocel(ocel)) => {
+                            filter.window = Some((&ocel.object, start, end));
+                        },
+                        _ => {
+                            share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                            return Err("A time window requires the ocel the ocdg was generated from".to_string());
+                        }
+                    }
+                }
+
+                if let Some(object_ids) = params.parameters[0]["string:ObjectIds"].as_str() {
+                    if !object_ids.is_empty() {
+                        let hops = params.parameters[0]["number:Hops"].as_f64().unwrap_or(1.0).max(0.0) as usize;
+                        filter.neighbourhood = Some((object_ids.split(";").collect(), hops));
+                    }
+                }
+                filter.drop_isolated = params.parameters[0]["bool:DropIsolated"].as_bool().unwrap_or(false);
+
+                share_progress("Extracting Subgraph", &mut curr_step, total_steps, &handler);
+                let subgraph = match object_graph::subgraph(&ocdg.object, &filter) {
+                    Ok(subgraph) => subgraph,
+                    Err(e) => {
+                        share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                        return Err(e);
+                    }
+                };
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("Subgraph of {}", ocdg.metadata["name"].as_str().unwrap_or_default())));
+                metadata.entry("type".to_string()).or_insert(Value::String("ocdg".to_string()));
+                metadata.entry("type-long".to_string()).or_insert(Value::String("Object-Centric Directed Graph".to_string()));
+                metadata.entry("file-type".to_string()).or_insert(Value::String("gexfocdg".to_string()));
+                instancedata.entry("ocdg-used".to_string()).or_insert(json!(ocdg.metadata["name"]));
+                for key in ["string:ObjectTypes", "string:Relations", "string:StartTime", "string:EndTime", "string:ObjectIds"] {
+                    if let Some(value) = params.parameters[0][key].as_str().filter(|v| !v.is_empty()) {
+                        instancedata.entry(key.trim_start_matches("string:").to_string()).or_insert(json!(value));
+                    }
+                }
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocdg(&subgraph)));
+                let new_ocdg = OcdgEntity {id, object: subgraph, metadata, instancedata, diff: None};
+                state.entry(id).or_insert(Entity::Ocdg(new_ocdg));
+            }
+        },
+        Plugins::OcdgDiff => {
+            let iocdgs: Vec<usize> = params.inputs.get("ocdg").map_or_else(Vec::new, |ids| ids.iter().map(|i| i.parse().unwrap()).collect());
+            if iocdgs.len() != 2 {
+                share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                return Err("Invalid Input OCDGs, exactly two OCDGs are compared".to_string());
+            }
+            // the dropdowns pick the old and the new graph out of the two selected OCDGs
+            let position = |key: &str| match params.parameters[0][key].as_str() {
+                Some("First selected") => Some(0),
+                Some("Second selected") => Some(1),
+                _ => None
+            };
+            let (iold, inew) = match (position("dropdown:OldOcdg"), position("dropdown:NewOcdg")) {
+                (Some(o), Some(n)) if o != n => (iocdgs[o], iocdgs[n]),
+                _ => {
+                    share_progress("Invalid Properties.. Cancelling..", &mut curr_step, total_steps, &handler);
+                    return Err("Invalid Input Old and New OCDG, they have to be different graphs".to_string());
+                }
+            };
+            let mut state = entitystate.0.lock().unwrap();
+            if let (Entity::Ocdg(old), Entity::Ocdg(new)) = (&state[&iold], &state[&inew]) {
+                share_progress("Comparing Graphs", &mut curr_step, total_steps, &handler);
+                let (node_changes, edge_changes) = object_graph::diff(&object_graph::ObjectGraph::from_ocdg(&old.object), &object_graph::ObjectGraph::from_ocdg(&new.object));
+
+                share_progress("Storing Results", &mut curr_step, total_steps, &handler);
+                let changed_nodes: Vec<&object_graph::NodeChange> = node_changes.iter().filter(|n| n.status != "unchanged").collect();
+                let changed_edges: Vec<&object_graph::EdgeChange> = edge_changes.iter().filter(|e| e.status != "unchanged").collect();
+                let df = DataFrame::new(vec![Series::new("Object", changed_nodes.iter().map(|n| n.object.as_str()).collect::<Vec<&str>>()),
+                                             Series::new("Object Type", changed_nodes.iter().map(|n| n.object_type.as_str()).collect::<Vec<&str>>()),
+                                             Series::new("Status", changed_nodes.iter().map(|n| n.status).collect::<Vec<&str>>())]).expect("Data Table Creation went wrong");
+                let edge_df = DataFrame::new(vec![Series::new("Source", changed_edges.iter().map(|e| e.source.as_str()).collect::<Vec<&str>>()),
+                                                  Series::new("Target", changed_edges.iter().map(|e| e.target.as_str()).collect::<Vec<&str>>()),
+                                                  Series::new("Relation", changed_edges.iter().map(|e| e.relation.as_str()).collect::<Vec<&str>>()),
+                                                  Series::new("Status", changed_edges.iter().map(|e| e.status).collect::<Vec<&str>>()),
+                                                  Series::new("Old Weight", changed_edges.iter().map(|e| e.old_weight as u32).collect::<Vec<u32>>()),
+                                                  Series::new("New Weight", changed_edges.iter().map(|e| e.new_weight as u32).collect::<Vec<u32>>())]).expect("Data Table Creation went wrong");
+
+                let (diff_ocdg, diff_status) = object_graph::union(&old.object, &new.object, &node_changes, &edge_changes);
+
+                let (old_name, new_name) = (old.metadata["name"].as_str().unwrap_or_default().to_string(), new.metadata["name"].as_str().unwrap_or_default().to_string());
+                let count = |changes: &[&str], status: &str| changes.iter().filter(|s| **s == status).count();
+                let node_statuses: Vec<&str> = changed_nodes.iter().map(|n| n.status).collect();
+                let edge_statuses: Vec<&str> = changed_edges.iter().map(|e| e.status).collect();
+                let summary = Map::from_iter([("ocdg-old".to_string(), json!(old_name)),
+                                              ("ocdg-new".to_string(), json!(new_name)),
+                                              ("Added Nodes".to_string(), json!(count(&node_statuses, "added"))),
+                                              ("Removed Nodes".to_string(), json!(count(&node_statuses, "removed"))),
+                                              ("Added Edges".to_string(), json!(count(&edge_statuses, "added"))),
+                                              ("Removed Edges".to_string(), json!(count(&edge_statuses, "removed"))),
+                                              ("Changed Edges".to_string(), json!(count(&edge_statuses, "changed")))]);
+
+                metadata.entry("name".to_string()).or_insert(json!(format!("Node Changes from {} to {}", old_name, new_name)));
+                metadata.entry("type".to_string()).or_insert(json!("table"));
+                metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+                instancedata.extend(summary.clone());
+                instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+                let columns = provenance::table_provenance(&df, "OcdgDiff", &iocdgs);
+                let new_table = TableEntity {id, object: df, metadata, instancedata, columns};
+                state.entry(id).or_insert(Entity::Table(new_table));
+
+                output_ids.push(store_table(format!("Edge Changes from {} to {}", old_name, new_name), edge_df, summary.clone(), "OcdgDiff", &iocdgs, &mut state));
+                let ocdg_id = get_new_id();
+                let mut ocdg_metadata = generate_default_metadata(ocdg_id);
+                ocdg_metadata.entry("name".to_string()).or_insert(json!(format!("Diff of {} and {}", old_name, new_name)));
+                ocdg_metadata.entry("type".to_string()).or_insert(json!("ocdg"));
+                ocdg_metadata.entry("type-long".to_string()).or_insert(json!("Object-Centric Directed Graph"));
+                ocdg_metadata.entry("file-type".to_string()).or_insert(json!("gexfocdg"));
+                let mut ocdg_instancedata = summary;
+                ocdg_instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocdg(&diff_ocdg)));
+                state.entry(ocdg_id).or_insert(Entity::Ocdg(OcdgEntity {id: ocdg_id, object: diff_ocdg, metadata: ocdg_metadata, instancedata: ocdg_instancedata, diff: Some(diff_status)}));
+                output_ids.push(ocdg_id);
+            }
+        },
+        // _ => {},
+    }
+
+    share_progress(format!("Finished Plugin: {}", plugin_info.name.as_str()).as_str(), &mut curr_step, total_steps, &handler);
+    Ok(output_ids.iter().map(|i| i.to_string()).collect())
+}
+
+fn get_plugin_info(plugin: &Plugins) -> Option<Plugin> {
+    match plugin {
+        Plugins::GenerateOcdg => {
+            let plug = r#"{
+                "id": 1,
+                "name": "Generate Ocdg",
+                "total_steps": 2,
+                "enumid": "GenerateOcdg",
+                "description": "Generate an Object-Centric Directed Graph with specified relations.",
+                "type": "Generation",
+                "input": {"ocel": 1},
+                "output": {"ocdg": 1},
+                "parameters": []
+            }"#;
+            let mut gen_ocdg: Plugin = serde_json::from_str(plug).unwrap();
+            let parameters: HashMap<String, Value> = HashMap::from([("multichoice:Relations".to_string(), serde_json::to_value(Relations::iter().map(|rel| format!("{:?}", rel)).collect::<Vec<String>>()).unwrap()), ("header".to_string(), Value::String("General".to_string()))]);
+            gen_ocdg.parameters.push(parameters);
+            return Some(gen_ocdg);
+        },
+        Plugins::ValidateOcel => {
+            let plug = r#"{
+                "id": 2,
+                "name": "Validate Ocel",
+                "total_steps": 2,
+                "enumid": "ValidateOcel",
+                "description": "Validates the OCEL input file and returns all errors that exist with the document",
+                "type": "Validation",
+                "input": {},
+                "output": {"table": 1},
+                "parameters": [{"header": "General", "file:ValidationFile": ""}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::AllObjectPointFeatures => {
+        let plug = r#"{
+                "id": 3,
+                "name": "Generate all Object Point Features (oid intersection)",
+                "total_steps": 2,
+                "enumid": "AllObjectPointFeatures",
+                "description": "Generate all object features based on default values. This plugin only returns features of objects that are in both the ocel and ocdg.",
+                "type": "Feature Extraction",
+                "input": {"ocel": 1, "ocdg": 1},
+                "output": {"table": 1},
+                "parameters": []
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::MergeFeaturesIntoOcel => {
+        let plug = r#"{
+                "id": 4,
+                "name": "Merge Feature Table into Ocel log",
+                "total_steps": 2,
+                "enumid": "MergeFeaturesIntoOcel",
+                "description": "Merge a feature table into an ocel log. If ConsumeEntities is true, the input objects are consumed to create the merged log. If false, the result is the result of cloning the data.",
+                "type": "Combination",
+                "input": {"ocel": 1, "table": 1},
+                "output": {"ocel": 1},
+                "parameters": [{"header": "General", "bool:ConsumeEntities": true}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+
+            
+        },
+        Plugins::UiDemo => {
+        let plug = r#"{
+                "id": 5,
+                "name": "UI Demo",
+                "total_steps": 0,
+                "enumid": "UiDemo",
+                "description": "Plugin to showcase all the user interface options that are available.",
+                "type": "Demo",
+                "input": {},
+                "output": {},
+                "parameters": [{"header": "General", "string:Normal String": "default input", "number: Number input": 123.321, "multichoice:Multiple Choice!!": ["multiple", "selection", "options"], "bool:boolean selection": false, "file:Select a file!": "", "dropdown:Drop down selection!": ["only", "select", "one"], "slider:slider min,max,step,initial": [0.0, 1.0, 0.01, 0.5]}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::OcelTimeSeries => {
+        let plug = r#"{
+                "id": 6,
+                "name": "Generate OCEL Timeseries",
+                "total_steps": 2,
+                "enumid": "OcelTimeSeries",
+                "description": "Generate one time series column per selected series type from the events of an OCEL.",
+                "type": "Generation",
+                "input": {"ocel": 1},
+                "output": {"table": 1},
+                "parameters": []
+            }"#;
+
+            let mut val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let parameters: HashMap<String, Value> = HashMap::from([("header".to_string(), json!("General")),
+                                                                    ("optional".to_string(), json!(["string:BinWidth", "string:StartTime", "string:EndTime"])),
+                                                                    ("multichoice:SeriesTypes".to_string(), json!(time_series::SERIES_TYPES)),
+                                                                    ("dropdown:Breakdown".to_string(), json!(time_series::BREAKDOWNS)),
+                                                                    ("string:BinWidth".to_string(), json!("")),
+                                                                    ("number:BinCount".to_string(), json!(0)),
+                                                                    ("string:StartTime".to_string(), json!("")),
+                                                                    ("string:EndTime".to_string(), json!(""))]);
+            val_ocel.parameters.push(parameters);
+            return Some(val_ocel);
+        },
+        Plugins::OcelObjectSituations => {
+        let plug = r#"{
+                "id": 7,
+                "name": "Object Situation Targets",
+                "total_steps": 2,
+                "enumid": "OcelObjectSituations",
+                "description": "Gather all object targets that contains a specific set of requirements.",
+                "type": "Generation",
+                "input": {"ocel": 1},
+                "output": {"table": 1},
+                "parameters": [{"header": "General", 
+                                "dropdown:SelectSituationType": ["ObjectAttribute", "ObjectAttributeUnknown", 
+                                                                 "ObjectMissingActivity", "ObjectLifetime", 
+                                                                 "ObjectMissingReachableObjectType"],
+                                "string:Activities": "",
+                                "string:Property": "",
+                                "string:ObjectTypes": ""}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+
+        },
+        Plugins::OcelEventSituations => {
+        let plug = r#"{
+                "id": 8,
+                "name": "Event Situation Targets",
+                "total_steps": 2,
+                "enumid": "OcelEventSituations",
+                "description": "Gather all event targets that contains a specific set of requirements.",
+                "type": "Generation",
+                "input": {"ocel": 1},
+                "output": {"table": 1},
+                "parameters": [{"header": "General", 
+                                "dropdown:SelectSituationType": ["EventChoice", "EventAttribute", 
+                                                                 "EventAttributeUnknown", "EventWait", 
+                                                                 "EventDuration", "EventObjectChoice",
+                                                                 "EventMissingRelation", "EventMissingObjectType"],
+                                "string:Activities": "",
+                                "string:Property": "",
+                                "string:ObjectTypes": "",
+                                "string:Relations": "",
+                                "number:DurationThreshold": 0}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        
+        },
+        Plugins::AllObjectGroupFeatures => {
+        let plug = r#"{
+                "id": 9,
+                "name": "Generate all Object Group Features",
+                "total_steps": 2,
+                "enumid": "AllObjectGroupFeatures",
+                "description": "Generate all global object features based on default values. This plugin only returns features based on objects that are in both the ocel and ocdg.",
+                "type": "Feature Extraction",
+                "input": {"ocel": 1, "ocdg": 1},
+                "output": {"table": 1},
+                "parameters": []
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::AllEventPointFeatures => {
+        let plug = r#"{
+                "id": 10,
+                "name": "Generate all Event Point Features",
+                "total_steps": 2,
+                "enumid": "AllEventPointFeatures",
+                "description": "Generate all point event features based on default values. This plugin only returns features based on events that are in both the ocel and ocdg.",
+                "type": "Feature Extraction",
+                "input": {"ocel": 1, "ocdg": 1},
+                "output": {"table": 1},
+                "parameters": []
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::AllEventGroupFeatures => {
+        let plug = r#"{
+                "id": 11,
+                "name": "Generate all Event Group Features",
+                "total_steps": 2,
+                "enumid": "AllEventGroupFeatures",
+                "description": "Generate all global event features based on default values. This plugin only returns features based on events/objects that are in both the ocel and ocdg.",
+                "type": "Feature Extraction",
+                "input": {"ocel": 1, "ocdg": 1},
+                "output": {"table": 1},
+                "parameters": []
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::DescribeOcel => {
+        let plug = r#"{
+                "id": 12,
+                "name": "Describe OCEL",
+                "total_steps": 2,
+                "enumid": "DescribeOcel",
+                "description": "Summarise an OCEL with activity, object type, attribute and co-occurrence statistics.",
+                "type": "Statistics",
+                "input": {"ocel": 1},
+                "output": {"table": 6},
+                "parameters": []
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::FilterOcel => {
+        let plug = r#"{
+                "id": 13,
+                "name": "Filter OCEL",
+                "total_steps": 2,
+                "enumid": "FilterOcel",
+                "description": "Create a sub-log of an OCEL by activity, object type, time range and event attributes (e.g. 'price>=10;status==done').",
+                "type": "Filtering",
+                "input": {"ocel": 1},
+                "output": {"ocel": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:IncludeActivities", "string:ExcludeActivities", "string:IncludeObjectTypes", "string:ExcludeObjectTypes", "string:StartTime", "string:EndTime", "string:AttributePredicates"],
+                                "string:IncludeActivities": "",
+                                "string:ExcludeActivities": "",
+                                "string:IncludeObjectTypes": "",
+                                "string:ExcludeObjectTypes": "",
+                                "string:StartTime": "",
+                                "string:EndTime": "",
+                                "string:AttributePredicates": ""}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::DiscoverOcDfg => {
+        let plug = r#"{
+                "id": 14,
+                "name": "Discover Object-Centric DFG",
+                "total_steps": 2,
+                "enumid": "DiscoverOcDfg",
+                "description": "Discover an object-centric directly-follows graph with frequencies and waiting times per object type.",
+                "type": "Discovery",
+                "input": {"ocel": 1},
+                "output": {"dfg": 1},
+                "parameters": [{"header": "General", "number:MinFrequency": 1}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
             return Some(val_ocel);
         },
-        Plugins::AllEventPointFeatures => {
+        Plugins::DiscoverOcPetriNet => {
         let plug = r#"{
-                "id": 10,
-                "name": "Generate all Event Point Features",
+                "id": 15,
+                "name": "Discover Object-Centric Petri Net",
                 "total_steps": 2,
-                "enumid": "AllEventPointFeatures",
-                "description": "Generate all point event features based on default values. This plugin only returns features based on events that are in both the ocel and ocdg.",
+                "enumid": "DiscoverOcPetriNet",
+                "description": "Discover an object-centric Petri net with the inductive miner for the given ObjectTypes (all if left empty).",
+                "type": "Discovery",
+                "input": {"ocel": 1},
+                "output": {"petrinet": 1},
+                "parameters": [{"header": "General", "optional": ["string:ObjectTypes"], "string:ObjectTypes": ""}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::TokenReplay => {
+        let plug = r#"{
+                "id": 16,
+                "name": "Token-Based Replay",
+                "total_steps": 3,
+                "enumid": "TokenReplay",
+                "description": "Replay the objects of an OCEL on an object-centric Petri net (PNML) and report fitness and precision.",
+                "type": "Conformance",
+                "input": {"ocel": 1},
+                "output": {"table": 1},
+                "parameters": [{"header": "General", "file:PetriNet": ""}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::ProcessExecutions => {
+        let plug = r#"{
+                "id": 17,
+                "name": "Process Execution Variants",
+                "total_steps": 3,
+                "enumid": "ProcessExecutions",
+                "description": "Split an OCEL into process executions and group them into variants by their event graphs.",
+                "type": "Variant Analysis",
+                "input": {"ocel": 1, "ocdg": [0, 1]},
+                "output": {"table": 2, "ocel": 1},
+                "parameters": [{"header": "General",
+                                "dropdown:ConnectedBy": ["SharedEvents", "Ocdg"],
+                                "number:MaterialiseVariant": 0}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::ObjectPointFeatures => {
+        let plug = r#"{
+                "id": 18,
+                "name": "Generate selected Object Point Features",
+                "total_steps": 2,
+                "enumid": "ObjectPointFeatures",
+                "description": "Generate the selected object point features for the given activity pairs ('src->tar'), object types and relations.",
                 "type": "Feature Extraction",
                 "input": {"ocel": 1, "ocdg": 1},
                 "output": {"table": 1},
                 "parameters": []
             }"#;
 
-            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let mut val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let all_features: Vec<&str> = features::OBJECT_POINT_SIMPLE.iter().chain(features::OBJECT_POINT_PARAMETERISED.iter()).copied().collect();
+            let parameters: HashMap<String, Value> = HashMap::from([("header".to_string(), json!("General")),
+                                                                    ("optional".to_string(), json!(["string:ActivityPairs", "string:ObjectTypes", "string:Relations"])),
+                                                                    ("multichoice:Features".to_string(), json!(all_features)),
+                                                                    ("string:ActivityPairs".to_string(), json!("")),
+                                                                    ("string:ObjectTypes".to_string(), json!("")),
+                                                                    ("string:Relations".to_string(), json!(""))]);
+            val_ocel.parameters.push(parameters);
             return Some(val_ocel);
         },
-        Plugins::AllEventGroupFeatures => {
+        Plugins::AttributeOperatorFeatures => {
         let plug = r#"{
-                "id": 11,
-                "name": "Generate all Event Group Features",
+                "id": 19,
+                "name": "Generate Attribute Operator Features",
                 "total_steps": 2,
-                "enumid": "AllEventGroupFeatures",
-                "description": "Generate all global event features based on default values. This plugin only returns features based on events/objects that are in both the ocel and ocdg.",
+                "enumid": "AttributeOperatorFeatures",
+                "description": "Aggregate numeric event attributes per object with the selected operators, optionally per activity.",
+                "type": "Feature Extraction",
+                "input": {"ocel": 1},
+                "output": {"table": 1},
+                "parameters": []
+            }"#;
+
+            let mut val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let parameters: HashMap<String, Value> = HashMap::from([("header".to_string(), json!("General")),
+                                                                    ("optional".to_string(), json!(["string:Attributes"])),
+                                                                    ("multichoice:Operators".to_string(), json!(Operator::iter().map(|op| op.to_string()).collect::<Vec<String>>())),
+                                                                    ("string:Attributes".to_string(), json!("")),
+                                                                    ("bool:PerActivity".to_string(), json!(false))]);
+            val_ocel.parameters.push(parameters);
+            return Some(val_ocel);
+        },
+        Plugins::EventGroupFeatures => {
+        let plug = r#"{
+                "id": 20,
+                "name": "Generate selected Event Group Features",
+                "total_steps": 2,
+                "enumid": "EventGroupFeatures",
+                "description": "Generate the selected event group features for the selected operators and activities, or only count their columns with DryRun.",
                 "type": "Feature Extraction",
                 "input": {"ocel": 1, "ocdg": 1},
                 "output": {"table": 1},
                 "parameters": []
             }"#;
 
+            let mut val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let parameters: HashMap<String, Value> = HashMap::from([("header".to_string(), json!("General")),
+                                                                    ("optional".to_string(), json!(["string:Activities"])),
+                                                                    ("multichoice:Features".to_string(), json!(features::EVENT_GROUP_FEATURES)),
+                                                                    ("multichoice:Operators".to_string(), json!(Operator::iter().map(|op| op.to_string()).collect::<Vec<String>>())),
+                                                                    ("string:Activities".to_string(), json!("")),
+                                                                    ("bool:DryRun".to_string(), json!(false))]);
+            val_ocel.parameters.push(parameters);
+            return Some(val_ocel);
+        },
+        Plugins::SituationTable => {
+        let plug = r#"{
+                "id": 21,
+                "name": "Build Situation Table",
+                "total_steps": 2,
+                "enumid": "SituationTable",
+                "description": "Join a situation target table with feature tables on their first column.",
+                "type": "Generation",
+                "input": {"table": [2, 9007199254740991]},
+                "output": {"table": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:TargetColumn"],
+                                "string:TargetColumn": "",
+                                "bool:DropMissingTargets": false}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::DecisionTree => {
+        let plug = r#"{
+                "id": 22,
+                "name": "Decision Tree",
+                "total_steps": 3,
+                "enumid": "DecisionTree",
+                "description": "Train a classification or regression tree on a table to explain the TargetColumn.",
+                "type": "Machine Learning",
+                "input": {"table": 1},
+                "output": {"table": 1, "model": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:FeatureColumns"],
+                                "string:TargetColumn": "",
+                                "string:FeatureColumns": "",
+                                "dropdown:Task": ["Auto", "Classification", "Regression"],
+                                "number:MaxDepth": 5,
+                                "number:MinSamplesLeaf": 5,
+                                "slider:TestFraction": [0.0, 0.5, 0.05, 0.2],
+                                "number:Seed": 42}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::ApplyModel => {
+        let plug = r#"{
+                "id": 23,
+                "name": "Apply Model",
+                "total_steps": 2,
+                "enumid": "ApplyModel",
+                "description": "Score a table with a trained model and append the predictions as a new column.",
+                "type": "Machine Learning",
+                "input": {"model": 1, "table": 1},
+                "output": {"table": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:PredictionColumn"],
+                                "string:PredictionColumn": ""}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::Clustering => {
+        let plug = r#"{
+                "id": 24,
+                "name": "Cluster Table Rows",
+                "total_steps": 3,
+                "enumid": "Clustering",
+                "description": "Cluster the rows of a table with k-means or DBSCAN, optionally splitting an OCEL per cluster.",
+                "type": "Machine Learning",
+                "input": {"table": 1, "ocel": [0, 1]},
+                "output": {"table": 2, "ocel": [0, 9007199254740991]},
+                "parameters": [{"header": "General",
+                                "optional": ["string:Columns"],
+                                "dropdown:Algorithm": ["KMeans", "DBSCAN"],
+                                "string:Columns": "",
+                                "bool:Standardise": false,
+                                "number:Clusters": 3,
+                                "number:MaxIterations": 100,
+                                "number:Seed": 42,
+                                "number:Epsilon": 0.5,
+                                "number:MinPoints": 5,
+                                "bool:SplitOcel": false}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::AnomalyDetection => {
+        let plug = r#"{
+                "id": 25,
+                "name": "Detect Anomalies",
+                "total_steps": 2,
+                "enumid": "AnomalyDetection",
+                "description": "Flag anomalous rows of a table, optionally tagging the objects of an OCEL.",
+                "type": "Machine Learning",
+                "input": {"table": 1, "ocel": [0, 1]},
+                "output": {"table": 1, "ocel": [0, 1]},
+                "parameters": [{"header": "General",
+                                "optional": ["string:Columns", "string:TagAttribute"],
+                                "dropdown:Method": ["IsolationForest", "ZScore", "IQR"],
+                                "string:Columns": "",
+                                "number:Trees": 100,
+                                "number:SampleSize": 256,
+                                "number:Seed": 42,
+                                "slider:IsolationThreshold": [0.5, 1.0, 0.01, 0.6],
+                                "number:ZThreshold": 3,
+                                "number:IqrFactor": 1.5,
+                                "bool:TagOcel": false,
+                                "string:TagAttribute": "anomaly"}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::Forecasting => {
+        let plug = r#"{
+                "id": 26,
+                "name": "Forecast Time Series",
+                "total_steps": 3,
+                "enumid": "Forecasting",
+                "description": "Forecast a numeric table column Horizon steps ahead with exponential smoothing or an autoregression.",
+                "type": "Machine Learning",
+                "input": {"table": 1},
+                "output": {"table": 2},
+                "parameters": [{"header": "General",
+                                "optional": ["string:Column"],
+                                "string:Column": "",
+                                "dropdown:Method": ["SimpleExponentialSmoothing", "Holt", "HoltWinters", "Autoregression"],
+                                "number:Horizon": 10,
+                                "number:Alpha": 0,
+                                "number:Beta": 0,
+                                "number:Gamma": 0,
+                                "number:SeasonLength": 7,
+                                "number:ArOrder": 2,
+                                "slider:Confidence": [0.5, 0.99, 0.01, 0.95],
+                                "number:BacktestSize": 0}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::DriftDetection => {
+        let plug = r#"{
+                "id": 27,
+                "name": "Detect Concept Drift",
+                "total_steps": 4,
+                "enumid": "DriftDetection",
+                "description": "Detect concept drift by comparing the behaviour of sliding OCEL windows with chi-squared tests.",
+                "type": "Statistics",
+                "input": {"ocel": 1},
+                "output": {"table": 2, "ocel": [0, 9007199254740991]},
+                "parameters": []
+            }"#;
+
+            let mut val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let parameters: HashMap<String, Value> = HashMap::from([("header".to_string(), json!("General")),
+                                                                    ("optional".to_string(), json!(["string:WindowSize", "string:WindowStep"])),
+                                                                    ("multichoice:Signatures".to_string(), json!(drift::SIGNATURES)),
+                                                                    ("string:WindowSize".to_string(), json!("")),
+                                                                    ("number:WindowCount".to_string(), json!(10)),
+                                                                    ("string:WindowStep".to_string(), json!("")),
+                                                                    ("slider:Significance".to_string(), json!([0.001, 0.1, 0.001, 0.01])),
+                                                                    ("slider:MinDistance".to_string(), json!([0.0, 1.0, 0.01, 0.1])),
+                                                                    ("bool:SplitOcel".to_string(), json!(false))]);
+            val_ocel.parameters.push(parameters);
+            return Some(val_ocel);
+        },
+        Plugins::Causality => {
+        let plug = r#"{
+                "id": 28,
+                "name": "Time Series Causality",
+                "total_steps": 3,
+                "enumid": "Causality",
+                "description": "Test which OCEL time series Granger-cause each other and return the significant pairs as a graph.",
+                "type": "Statistics",
+                "input": {"table": 1},
+                "output": {"table": 1, "graph": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:Columns"],
+                                "string:Columns": "",
+                                "number:MaxLag": 3,
+                                "slider:Significance": [0.001, 0.1, 0.001, 0.05],
+                                "bool:Difference": false}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::OcdgCentrality => {
+        let plug = r#"{
+                "id": 29,
+                "name": "OCDG Centralities",
+                "total_steps": 2,
+                "enumid": "OcdgCentrality",
+                "description": "Compute degree, PageRank, betweenness, clustering and component measures per object of an OCDG.",
+                "type": "Feature Extraction",
+                "input": {"ocdg": 1},
+                "output": {"table": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:Relations"],
+                                "string:Relations": "",
+                                "slider:Damping": [0.5, 0.99, 0.01, 0.85],
+                                "number:MaxIterations": 100,
+                                "bool:SkipBetweenness": false}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::OcdgCommunities => {
+        let plug = r#"{
+                "id": 30,
+                "name": "OCDG Communities",
+                "total_steps": 3,
+                "enumid": "OcdgCommunities",
+                "description": "Find communities of interacting objects in an OCDG with Louvain or label propagation.",
+                "type": "Discovery",
+                "input": {"ocdg": 1},
+                "output": {"table": 2},
+                "parameters": []
+            }"#;
+
+            let mut val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            let parameters: HashMap<String, Value> = HashMap::from([("header".to_string(), json!("General")),
+                                                                    ("optional".to_string(), json!(["string:RelationWeights"])),
+                                                                    ("dropdown:Algorithm".to_string(), json!(community::ALGORITHMS)),
+                                                                    ("string:RelationWeights".to_string(), json!("")),
+                                                                    ("number:Resolution".to_string(), json!(1)),
+                                                                    ("number:MaxIterations".to_string(), json!(100)),
+                                                                    ("number:Seed".to_string(), json!(42))]);
+            val_ocel.parameters.push(parameters);
+            return Some(val_ocel);
+        },
+        Plugins::OcdgSubgraph => {
+        let plug = r#"{
+                "id": 31,
+                "name": "Extract OCDG Subgraph",
+                "total_steps": 2,
+                "enumid": "OcdgSubgraph",
+                "description": "Restrict an OCDG to object types, relations, a time window or the neighbourhood of objects.",
+                "type": "Filtering",
+                "input": {"ocdg": 1, "ocel": [0, 1]},
+                "output": {"ocdg": 1},
+                "parameters": [{"header": "General",
+                                "optional": ["string:ObjectTypes", "string:Relations", "string:StartTime", "string:EndTime", "string:ObjectIds"],
+                                "string:ObjectTypes": "",
+                                "string:Relations": "",
+                                "string:StartTime": "",
+                                "string:EndTime": "",
+                                "string:ObjectIds": "",
+                                "number:Hops": 1,
+                                "bool:DropIsolated": false}]
+            }"#;
+
+            let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
+            return Some(val_ocel);
+        },
+        Plugins::OcdgDiff => {
+        let plug = r#"{
+                "id": 32,
+                "name": "Compare OCDGs",
+                "total_steps": 3,
+                "enumid": "OcdgDiff",
+                "description": "Compare an old and a new OCDG and return their changes and a diff OCDG with the union of both.",
+                "type": "Combination",
+                "input": {"ocdg": 2},
+                "output": {"table": 2, "ocdg": 1},
+                "parameters": [{"header": "General", "dropdown:OldOcdg": ["First selected", "Second selected"], "dropdown:NewOcdg": ["Second selected", "First selected"]}]
+            }"#;
+
             let val_ocel: Plugin = serde_json::from_str(plug).expect("This should never crash");
             return Some(val_ocel);
         },
@@ -792,6 +2738,22 @@ impl Entity {
             Entity::Table(ent) => {
                 instance.entry("metadata".to_string()).or_insert(serde_json::Value::Object(ent.metadata.clone()));
                 instance.entry("instancedata".to_string()).or_insert(Value::Object(ent.instancedata.clone()));
+            },
+            Entity::Dfg(ent) => {
+                instance.entry("metadata".to_string()).or_insert(serde_json::Value::Object(ent.metadata.clone()));
+                instance.entry("instancedata".to_string()).or_insert(Value::Object(ent.instancedata.clone()));
+            },
+            Entity::PetriNet(ent) => {
+                instance.entry("metadata".to_string()).or_insert(serde_json::Value::Object(ent.metadata.clone()));
+                instance.entry("instancedata".to_string()).or_insert(Value::Object(ent.instancedata.clone()));
+            },
+            Entity::Model(ent) => {
+                instance.entry("metadata".to_string()).or_insert(serde_json::Value::Object(ent.metadata.clone()));
+                instance.entry("instancedata".to_string()).or_insert(Value::Object(ent.instancedata.clone()));
+            },
+            Entity::Graph(ent) => {
+                instance.entry("metadata".to_string()).or_insert(serde_json::Value::Object(ent.metadata.clone()));
+                instance.entry("instancedata".to_string()).or_insert(Value::Object(ent.instancedata.clone()));
             }
         }
             
@@ -822,6 +2784,24 @@ impl Entity {
                         Err(e) => {return Err(e.into());}
                     }
                 }
+            },
+            Entity::Dfg(ent) => {
+                if ent.object.edges.len() < 500 {
+                    return Ok(ent.object.to_dot());
+                }
+            },
+            Entity::PetriNet(ent) => {
+                if ent.object.arcs.len() < 1000 {
+                    return Ok(ent.object.to_dot());
+                }
+            },
+            Entity::Model(ent) => {
+                return Ok(ent.object.to_dot());
+            },
+            Entity::Graph(ent) => {
+                if ent.object.edges.len() < 1000 {
+                    return Ok(ent.object.to_dot());
+                }
             }
         }
 
@@ -836,20 +2816,53 @@ pub struct OcelEntity {
     pub id: usize,
     pub object: Ocel,
     metadata: Map<String, Value>,
-    instancedata: Map<String, Value>
+    instancedata: Map<String, Value>,
+    // provenance of the object attributes that were merged in from tables
+    attributes: Vec<ColumnProvenance>
 }
 
 pub struct OcdgEntity {
     pub id: usize,
     pub object: Ocdg,
     metadata: Map<String, Value>,
-    instancedata: Map<String, Value>
+    instancedata: Map<String, Value>,
+    // status of the objects and relations if the OCDG is the diff of two OCDGs
+    diff: Option<object_graph::DiffStatus>
 }
 
 pub struct TableEntity {
     pub id: usize,
     pub object: DataFrame,
     metadata: Map<String, Value>,
+    instancedata: Map<String, Value>,
+    columns: Vec<ColumnProvenance>
+}
+
+pub struct DfgEntity {
+    pub id: usize,
+    pub object: OcDfg,
+    metadata: Map<String, Value>,
+    instancedata: Map<String, Value>
+}
+
+pub struct PetriNetEntity {
+    pub id: usize,
+    pub object: OcPetriNet,
+    metadata: Map<String, Value>,
+    instancedata: Map<String, Value>
+}
+
+pub struct ModelEntity {
+    pub id: usize,
+    pub object: Model,
+    metadata: Map<String, Value>,
+    instancedata: Map<String, Value>
+}
+
+pub struct GraphEntity {
+    pub id: usize,
+    pub object: Graph,
+    metadata: Map<String, Value>,
     instancedata: Map<String, Value>
 }
 
@@ -873,18 +2886,56 @@ fn export_entity(rust_id: usize, filepath: &str, entitystate: tauri::State<Entit
     if let Some(entity) = rust_objs.get_mut(&rust_id) {
         match entity {
             Entity::Ocel(ocel) => {
-                match export_ocel_pretty(&ocel.object, filepath) {
-                    Ok(_) => {return Ok(filepath.to_string())},
-                    Err(e) => {return Err(e.to_string())}
+                if let Err(e) = export_ocel_pretty(&ocel.object, filepath) {
+                    return Err(e.to_string());
+                }
+                // provenance of merged attributes is kept next to the log as <file>.provenance.json
+                if !ocel.attributes.is_empty() {
+                    let sidecar = sidecar_path(filepath, "provenance");
+                    let provenance = serde_json::to_string_pretty(&ocel.attributes).map_err(|e| e.to_string())?;
+                    if let Err(e) = fs::write(&sidecar, provenance) {
+                        return Err(e.to_string());
+                    }
                 }
+                return Ok(filepath.to_string());
             },
             Entity::Ocdg(ocdg) => {
-                match export_ocdg(&ocdg.object, filepath) {
+                // the plain formats carry the status of a diff OCDG
+                let graph = object_graph::ObjectGraph::from_ocdg(&ocdg.object).with_status(ocdg.diff.as_ref());
+                let content = match Path::new(filepath).extension().and_then(|e| e.to_str()) {
+                    Some("graphml") => ocdg_export::to_graphml(&graph),
+                    Some("dot") | Some("gv") => ocdg_export::to_dot(&graph),
+                    Some("cyjs") => serde_json::to_string_pretty(&ocdg_export::to_cytoscape(&graph)).map_err(|e| e.to_string())?,
+                    Some(ext @ "csv") | Some(ext @ "parquet") => {
+                        let mut df = ocdg_export::edge_list(&graph);
+                        let output_file = OpenOptions::new().create(true).write(true).truncate(true).open(filepath).map_err(|e| e.to_string())?;
+                        if ext == "parquet" {
+                            ParquetWriter::new(output_file).finish(&mut df).map_err(|e| e.to_string())?;
+                        } else {
+                            CsvWriter::new(output_file).has_header(true).with_delimiter(b'|').finish(&mut df).map_err(|e| e.to_string())?;
+                        }
+                        return Ok(filepath.to_string());
+                    },
+                    Some("gexf") | Some("gexfocdg") => {
+                        if let Err(e) = export_ocdg(&ocdg.object, filepath) {
+                            return Err(e.to_string());
+                        }
+                        // the status of a diff OCDG is kept next to the graph as <file>.diff.json
+                        if let Some(status) = &ocdg.diff {
+                            let sidecar = sidecar_path(filepath, "diff");
+                            let content = serde_json::to_string_pretty(&status.to_json()).map_err(|e| e.to_string())?;
+                            if let Err(e) = fs::write(&sidecar, content) {
+                                return Err(e.to_string());
+                            }
+                        }
+                        return Ok(filepath.to_string());
+                    },
+                    _ => {return Err("File Extension Fail. OCDGs are exported as gexf, gexfocdg, graphml, dot, gv, cyjs, csv or parquet.".to_string())}
+                };
+                match fs::write(filepath, content) {
                     Ok(_) => {return Ok(filepath.to_string())},
                     Err(e) => {return Err(e.to_string())}
                 }
-
-
             },
             Entity::Table(table) => {
                 match OpenOptions::new().create(true).write(true).truncate(true).open(filepath) {
@@ -894,10 +2945,58 @@ fn export_entity(rust_id: usize, filepath: &str, entitystate: tauri::State<Entit
                             .with_delimiter(b'|')
                             .finish(&mut table.object).unwrap();
 
+                            // column provenance is kept next to the table as <file>.provenance.json
+                            let sidecar = sidecar_path(filepath, "provenance");
+                            let provenance = serde_json::to_string_pretty(&table.columns).map_err(|e| e.to_string())?;
+                            if let Err(e) = fs::write(&sidecar, provenance) {
+                                return Err(e.to_string());
+                            }
+
                             return Ok(filepath.to_string());
                     },
                     Err(e) => {return Err(e.to_string())}
                 }
+            },
+            Entity::Dfg(dfg) => {
+                let content = match Path::new(filepath).extension().and_then(|e| e.to_str()) {
+                    Some("dot") | Some("gv") => dfg.object.to_dot(),
+                    _ => serde_json::to_string_pretty(&dfg.object).map_err(|e| e.to_string())?
+                };
+                match fs::write(filepath, content) {
+                    Ok(_) => {return Ok(filepath.to_string())},
+                    Err(e) => {return Err(e.to_string())}
+                }
+            },
+            Entity::PetriNet(net) => {
+                let content = match Path::new(filepath).extension().and_then(|e| e.to_str()) {
+                    Some("pnml") => net.object.to_pnml(),
+                    Some("dot") | Some("gv") => net.object.to_dot(),
+                    _ => serde_json::to_string_pretty(&net.object).map_err(|e| e.to_string())?
+                };
+                match fs::write(filepath, content) {
+                    Ok(_) => {return Ok(filepath.to_string())},
+                    Err(e) => {return Err(e.to_string())}
+                }
+            },
+            Entity::Model(model) => {
+                let content = match Path::new(filepath).extension().and_then(|e| e.to_str()) {
+                    Some("dot") | Some("gv") => model.object.to_dot(),
+                    _ => serde_json::to_string_pretty(&model.object).map_err(|e| e.to_string())?
+                };
+                match fs::write(filepath, content) {
+                    Ok(_) => {return Ok(filepath.to_string())},
+                    Err(e) => {return Err(e.to_string())}
+                }
+            },
+            Entity::Graph(graph) => {
+                let content = match Path::new(filepath).extension().and_then(|e| e.to_str()) {
+                    Some("dot") | Some("gv") => graph.object.to_dot(),
+                    _ => serde_json::to_string_pretty(&graph.object).map_err(|e| e.to_string())?
+                };
+                match fs::write(filepath, content) {
+                    Ok(_) => {return Ok(filepath.to_string())},
+                    Err(e) => {return Err(e.to_string())}
+                }
             }
         }
     }
@@ -934,7 +3033,12 @@ fn import_entity(filepath: &str, entitystate: tauri::State<EntityState>) -> Resu
                         metadata.entry("file-type".to_string()).or_insert(Value::String("jsonocel".to_string()));
                         instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocel(&ocel)));
 
-                        let ocel_entity = OcelEntity {id, object: ocel, metadata, instancedata};
+                        // attribute provenance written next to an exported log is picked up again
+                        let attributes: Vec<ColumnProvenance> = match fs::read_to_string(sidecar_path(filepath, "provenance")) {
+                            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+                            Err(_) => vec![]
+                        };
+                        let ocel_entity = OcelEntity {id, object: ocel, metadata, instancedata, attributes};
 
                         let mut state = entitystate.0.lock().unwrap();
                         
@@ -955,7 +3059,11 @@ fn import_entity(filepath: &str, entitystate: tauri::State<EntityState>) -> Resu
                             metadata.entry("file-type".to_string()).or_insert(Value::String("gexfocdg".to_string()));
                             instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocdg(&ocdg)));
 
-                            let ocdg_entity = OcdgEntity {id, object: ocdg, metadata, instancedata};
+                            // the status written next to an exported diff OCDG is picked up again
+                            let diff = fs::read_to_string(sidecar_path(filepath, "diff")).ok()
+                                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                                .map(|status| object_graph::DiffStatus::from_json(&status));
+                            let ocdg_entity = OcdgEntity {id, object: ocdg, metadata, instancedata, diff};
 
                             let mut state = entitystate.0.lock().unwrap();
 
@@ -968,6 +3076,42 @@ fn import_entity(filepath: &str, entitystate: tauri::State<EntityState>) -> Resu
                         }
                     }
                 }
+                "json" => {
+                    match import_model(&filepath) {
+                        Ok(model) => {
+                            metadata.entry("type".to_string()).or_insert(Value::String("model".to_string()));
+                            metadata.entry("type-long".to_string()).or_insert(Value::String("Trained Model".to_string()));
+                            metadata.entry("file-type".to_string()).or_insert(Value::String("json".to_string()));
+                            instancedata.extend(generate_default_instance_data(EntityPrimitive::Model(&model)));
+
+                            let model_entity = ModelEntity {id, object: model, metadata, instancedata};
+
+                            let mut state = entitystate.0.lock().unwrap();
+
+                            state.entry(id).or_insert(Entity::Model(model_entity));
+                            Ok(id.to_string())
+                        },
+                        // json files that are not models can still be graphs
+                        Err(model_err) => match import_graph(&filepath) {
+                            Ok(graph) => {
+                                metadata.entry("type".to_string()).or_insert(Value::String("graph".to_string()));
+                                metadata.entry("type-long".to_string()).or_insert(Value::String("Graph".to_string()));
+                                metadata.entry("file-type".to_string()).or_insert(Value::String("json".to_string()));
+                                instancedata.extend(generate_default_instance_data(EntityPrimitive::Graph(&graph)));
+
+                                let graph_entity = GraphEntity {id, object: graph, metadata, instancedata};
+
+                                let mut state = entitystate.0.lock().unwrap();
+
+                                state.entry(id).or_insert(Entity::Graph(graph_entity));
+                                Ok(id.to_string())
+                            },
+                            Err(e) => {
+                                Err(format!("{:?} -> {:?} / {:?}", "File Import Fail", model_err, e).to_string())
+                            }
+                        }
+                    }
+                },
                 _ => {Err("File Extension Fail.".to_string())},
             }
         },
@@ -976,6 +3120,65 @@ fn import_entity(filepath: &str, entitystate: tauri::State<EntityState>) -> Resu
     
 }
 
+// files written next to an export, e.g. the provenance of out.csv is kept in out.csv.provenance.json
+fn sidecar_path(filepath: &str, kind: &str) -> String {
+    format!("{}.{}.json", filepath, kind)
+}
+
+fn generate_default_metadata(id: usize) -> Map<String, Value> {
+    let mut metadata = Map::<String, Value>::new();
+    metadata.entry("rust-id".to_string()).or_insert(Value::String(id.to_string()));
+    metadata.entry("time-created".to_string()).or_insert(Value::String(Local::now().to_string()));
+    metadata
+}
+
+// used by plugins that output more than one table
+fn store_table(name: String, df: DataFrame, mut instancedata: Map<String, Value>, family: &str, sources: &[usize], state: &mut HashMap<usize, Entity>) -> usize {
+    let id = get_new_id();
+    let mut metadata = generate_default_metadata(id);
+    metadata.entry("name".to_string()).or_insert(json!(name));
+    metadata.entry("type".to_string()).or_insert(json!("table"));
+    metadata.entry("type-long".to_string()).or_insert(json!("DataFrame"));
+    instancedata.extend(generate_default_instance_data(EntityPrimitive::Table(&df)));
+    let columns = provenance::table_provenance(&df, family, sources);
+    state.entry(id).or_insert(Entity::Table(TableEntity {id, object: df, metadata, instancedata, columns}));
+    id
+}
+
+fn store_ocel(name: String, ocel: Ocel, mut instancedata: Map<String, Value>, attributes: Vec<ColumnProvenance>, state: &mut HashMap<usize, Entity>) -> usize {
+    let id = get_new_id();
+    let mut metadata = generate_default_metadata(id);
+    metadata.entry("name".to_string()).or_insert(json!(name));
+    metadata.entry("type".to_string()).or_insert(json!("ocel"));
+    metadata.entry("type-long".to_string()).or_insert(json!("Object-Centric Event Log"));
+    metadata.entry("file-type".to_string()).or_insert(json!("jsonocel"));
+    instancedata.extend(generate_default_instance_data(EntityPrimitive::Ocel(&ocel)));
+    state.entry(id).or_insert(Entity::Ocel(OcelEntity {id, object: ocel, metadata, instancedata, attributes}));
+    id
+}
+
+fn store_model(name: String, model: Model, mut instancedata: Map<String, Value>, state: &mut HashMap<usize, Entity>) -> usize {
+    let id = get_new_id();
+    let mut metadata = generate_default_metadata(id);
+    metadata.entry("name".to_string()).or_insert(json!(name));
+    metadata.entry("type".to_string()).or_insert(json!("model"));
+    metadata.entry("type-long".to_string()).or_insert(json!("Trained Model"));
+    instancedata.extend(generate_default_instance_data(EntityPrimitive::Model(&model)));
+    state.entry(id).or_insert(Entity::Model(ModelEntity {id, object: model, metadata, instancedata}));
+    id
+}
+
+fn store_graph(name: String, graph: Graph, mut instancedata: Map<String, Value>, state: &mut HashMap<usize, Entity>) -> usize {
+    let id = get_new_id();
+    let mut metadata = generate_default_metadata(id);
+    metadata.entry("name".to_string()).or_insert(json!(name));
+    metadata.entry("type".to_string()).or_insert(json!("graph"));
+    metadata.entry("type-long".to_string()).or_insert(json!("Graph"));
+    instancedata.extend(generate_default_instance_data(EntityPrimitive::Graph(&graph)));
+    state.entry(id).or_insert(Entity::Graph(GraphEntity {id, object: graph, metadata, instancedata}));
+    id
+}
+
 fn generate_default_instance_data(entity: EntityPrimitive) -> Vec<(String, Value)> {
     let mut instancedata: Vec<(String, Value)> = vec![];
     match entity {
@@ -992,6 +3195,27 @@ fn generate_default_instance_data(entity: EntityPrimitive) -> Vec<(String, Value
         EntityPrimitive::Table(df) => {
             instancedata.push(("rows".to_string(), json!(df.shape().0)));
             instancedata.push(("columns".to_string(), json!(df.shape().1)));
+        },
+        EntityPrimitive::Dfg(dfg) => {
+            instancedata.push(("Activities".to_string(), json!(dfg.activities.len())));
+            instancedata.push(("Edge #".to_string(), json!(dfg.edges.len())));
+            instancedata.push(("Object Types".to_string(), json!(dfg.object_types())));
+        },
+        EntityPrimitive::PetriNet(net) => {
+            instancedata.push(("Place #".to_string(), json!(net.places.len())));
+            instancedata.push(("Transition #".to_string(), json!(net.transitions.len())));
+            instancedata.push(("Arc #".to_string(), json!(net.arcs.len())));
+            instancedata.push(("Object Types".to_string(), json!(net.object_types())));
+        },
+        EntityPrimitive::Model(model) => {
+            instancedata.push(("Model Type".to_string(), json!(model.kind())));
+            instancedata.push(("Target".to_string(), json!(model.target())));
+            instancedata.push(("Features".to_string(), json!(model.features())));
+        },
+        EntityPrimitive::Graph(graph) => {
+            instancedata.push(("Node #".to_string(), json!(graph.nodes.len())));
+            instancedata.push(("Edge #".to_string(), json!(graph.edges.len())));
+            instancedata.push(("Directed".to_string(), json!(graph.directed)));
         }
     }
     instancedata
@@ -1010,12 +3234,47 @@ fn get_view(rust_id: usize, entitystate: tauri::State<EntityState>) -> Result<St
                 Ok(v) => {return Ok(v);},
                 Err(e) => {return Err(e.to_string());}
                 }
+            },
+            Entity::Dfg(dfg) => {
+            match serde_json::to_string(&dfg.object) {
+                Ok(v) => {return Ok(v);},
+                Err(e) => {return Err(e.to_string());}
+                }
+            },
+            Entity::PetriNet(net) => {
+            match serde_json::to_string(&net.object) {
+                Ok(v) => {return Ok(v);},
+                Err(e) => {return Err(e.to_string());}
+                }
+            },
+            Entity::Model(model) => {
+            match serde_json::to_string(&model.object) {
+                Ok(v) => {return Ok(v);},
+                Err(e) => {return Err(e.to_string());}
+                }
+            },
+            Entity::Graph(graph) => {
+            match serde_json::to_string(&graph.object) {
+                Ok(v) => {return Ok(v);},
+                Err(e) => {return Err(e.to_string());}
+                }
             }
         }
     }
     Err("rust-id does not exist?!".to_string())
 }
 
+#[tauri::command]
+fn get_column_provenance(rust_id: usize, entitystate: tauri::State<EntityState>) -> Result<Vec<ColumnProvenance>, String> {
+    let state = entitystate.0.lock().unwrap();
+    match state.get(&rust_id) {
+        Some(Entity::Table(table)) => Ok(table.columns.clone()),
+        Some(Entity::Ocel(ocel)) => Ok(ocel.attributes.clone()),
+        Some(_) => Err("Column provenance is only available for tables and object attributes of logs".to_string()),
+        None => Err(format!("The rust id {} could not be found", rust_id))
+    }
+}
+
 #[tauri::command]
 fn get_instance_info(instance_id: usize, entitystate: tauri::State<EntityState>) -> Result<HashMap<String, Value>, String> {
     let state = entitystate.0.lock().unwrap();
@@ -1041,7 +3300,7 @@ fn main() {
   let context = tauri::generate_context!();
   tauri::Builder::default()
     .manage(EntityState(Default::default()))
-    .invoke_handler(tauri::generate_handler![import_entity, export_entity, get_instance_info, get_analysis_view, get_plugins, get_view, activate_plugin])
+    .invoke_handler(tauri::generate_handler![import_entity, export_entity, get_instance_info, get_analysis_view, get_plugins, get_view, activate_plugin, get_column_provenance])
     .menu(tauri::Menu::os_default(&context.package_info().name))
     .run(context)
     .expect("error while running tauri application");
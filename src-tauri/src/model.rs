@@ -0,0 +1,64 @@
+use std::fs;
+use polars::prelude::{DataFrame, Series, NamedFrom};
+use serde::{Serialize, Deserialize};
+use crate::tables::numeric_column;
+use crate::tree::{DecisionTree, Task};
+
+
+// trained models that can be stored in the workspace and applied to tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "model")]
+pub enum Model {
+    DecisionTree(DecisionTree)
+}
+
+impl Model {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Model::DecisionTree(_) => "DecisionTree"
+        }
+    }
+
+    // columns a table needs to be scored by the model
+    pub fn features(&self) -> &[String] {
+        match self {
+            Model::DecisionTree(tree) => &tree.features
+        }
+    }
+
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            Model::DecisionTree(tree) => Some(tree.target.as_str())
+        }
+    }
+
+    // the feature columns of the table in model order, fails if one is missing or not numeric
+    fn feature_matrix(&self, df: &DataFrame) -> Result<Vec<Vec<f64>>, String> {
+        self.features().iter().map(|name| {
+            let series = df.column(name).map_err(|_| format!("The table has no column {} required by the model", name))?;
+            numeric_column(series).ok_or(format!("Column {} is not numeric", name))
+        }).collect()
+    }
+
+    pub fn predict(&self, df: &DataFrame, name: &str) -> Result<Series, String> {
+        let x = self.feature_matrix(df)?;
+        let row = |r: usize| -> Vec<f64> { x.iter().map(|col| col[r]).collect() };
+        match self {
+            Model::DecisionTree(tree) => match tree.task {
+                Task::Classification => Ok(Series::new(name, (0..df.height()).map(|r| tree.label(tree.predict(&row(r)))).collect::<Vec<String>>())),
+                Task::Regression => Ok(Series::new(name, (0..df.height()).map(|r| tree.predict(&row(r))).collect::<Vec<f64>>()))
+            }
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        match self {
+            Model::DecisionTree(tree) => tree.to_dot()
+        }
+    }
+}
+
+pub fn import_model(path: &str) -> Result<Model, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Not a model file: {}", e))
+}
@@ -0,0 +1,222 @@
+// numerical helpers shared by the forecasting, drift detection and causality plugins
+
+
+// Ordinary least squares through the normal equations, `rows` already contain the intercept
+// column if one is wanted. None if the system is singular.
+pub fn least_squares(rows: &[Vec<f64>], y: &[f64]) -> Option<Vec<f64>> {
+    let k = rows.first()?.len();
+    let mut a: Vec<Vec<f64>> = vec![vec![0.0; k + 1]; k];
+    for (row, target) in rows.iter().zip(y) {
+        for i in 0..k {
+            for j in 0..k {
+                a[i][j] += row[i] * row[j];
+            }
+            a[i][k] += row[i] * target;
+        }
+    }
+
+    // gaussian elimination with partial pivoting
+    for col in 0..k {
+        let pivot = (col..k).max_by(|x, y| a[*x][col].abs().partial_cmp(&a[*y][col].abs()).unwrap_or(std::cmp::Ordering::Equal))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        for row in 0..k {
+            if row != col {
+                let factor = a[row][col] / a[col][col];
+                let pivot_row = a[col].clone();
+                a[row].iter_mut().zip(&pivot_row).skip(col).for_each(|(value, pivot)| *value -= factor * pivot);
+            }
+        }
+    }
+    Some((0..k).map(|i| a[i][k] / a[i][i]).collect())
+}
+
+pub fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len().max(1) as f64
+}
+
+pub fn variance(values: &[f64]) -> f64 {
+    let m = mean(values);
+    values.iter().map(|v| (v - m).powi(2)).sum::<f64>() / values.len().max(1) as f64
+}
+
+// Pearson correlation, 0 if one of the series is constant
+pub fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let (ma, mb) = (mean(a), mean(b));
+    let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+    let (va, vb): (f64, f64) = (a.iter().map(|x| (x - ma).powi(2)).sum(), b.iter().map(|y| (y - mb).powi(2)).sum());
+    if va <= 0.0 || vb <= 0.0 { 0.0 } else { cov / (va * vb).sqrt() }
+}
+
+// upper tail probability of the chi-squared distribution
+pub fn chi_squared_sf(statistic: f64, dof: usize) -> f64 {
+    if dof == 0 {
+        return 1.0;
+    }
+    upper_incomplete_gamma(dof as f64 / 2.0, statistic / 2.0)
+}
+
+// inverse of the standard normal cdf (Acklam's rational approximation)
+pub fn normal_quantile(p: f64) -> f64 {
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let a = [-39.696_830_286_653_76, 220.946_098_424_520_5, -275.928_510_446_968_7, 138.357_751_867_269, -30.664_798_066_147_16, 2.506_628_277_459_239];
+    let b = [-54.476_098_798_224_06, 161.585_836_858_040_9, -155.698_979_859_886_6, 66.801_311_887_719_72, -13.280_681_552_885_72];
+    let c = [-0.007_784_894_002_430_293, -0.322_396_458_041_136_5, -2.400_758_277_161_838, -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783];
+    let d = [0.007_784_695_709_041_462, 0.322_467_129_070_039_8, 2.445_134_137_142_996, 3.754_408_661_907_416];
+    let low = 0.024_25;
+
+    if p < low {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((c[0] * q + c[1]) * q + c[2]) * q + c[3]) * q + c[4]) * q + c[5]) / ((((d[0] * q + d[1]) * q + d[2]) * q + d[3]) * q + 1.0)
+    } else if p <= 1.0 - low {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((a[0] * r + a[1]) * r + a[2]) * r + a[3]) * r + a[4]) * r + a[5]) * q / (((((b[0] * r + b[1]) * r + b[2]) * r + b[3]) * r + b[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+// log of the gamma function for x > 0 (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    let coefficients = [76.180_091_729_471_46, -86.505_320_329_416_77, 24.014_098_240_830_91, -1.231_739_572_450_155, 0.001_208_650_973_866_179, -0.000_005_395_239_384_953];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let series = coefficients.iter().enumerate().fold(1.000_000_000_190_015, |acc, (i, c)| acc + c / (x + 1.0 + i as f64));
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+// continued fraction of the incomplete beta function (modified Lentz)
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    d = 1.0 / if d.abs() < tiny { tiny } else { d };
+    let mut result = d;
+    for m in 1..200 {
+        let m = m as f64;
+        for numerator in [m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)), -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0))] {
+            d = 1.0 + numerator * d;
+            d = 1.0 / if d.abs() < tiny { tiny } else { d };
+            c = 1.0 + numerator / c;
+            c = if c.abs() < tiny { tiny } else { c };
+            result *= c * d;
+        }
+        if (c * d - 1.0).abs() < 3e-14 {
+            break;
+        }
+    }
+    result
+}
+
+// regularized incomplete beta function I_x(a, b)
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+// regularized upper incomplete gamma function Q(a, x), from the series below a + 1 and the
+// continued fraction (modified Lentz) above
+fn upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let front = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..500 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 3e-14 {
+                break;
+            }
+        }
+        1.0 - front * sum
+    } else {
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut result = d;
+        for n in 1..500 {
+            let numerator = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = numerator * d + b;
+            d = 1.0 / if d.abs() < tiny { tiny } else { d };
+            c = b + numerator / c;
+            c = if c.abs() < tiny { tiny } else { c };
+            result *= c * d;
+            if (c * d - 1.0).abs() < 3e-14 {
+                break;
+            }
+        }
+        front * result
+    }
+}
+
+// upper tail probability of the F distribution
+pub fn f_sf(statistic: f64, dof1: usize, dof2: usize) -> f64 {
+    if statistic <= 0.0 || dof1 == 0 || dof2 == 0 {
+        return 1.0;
+    }
+    let (d1, d2) = (dof1 as f64, dof2 as f64);
+    incomplete_beta(d2 / 2.0, d1 / 2.0, d2 / (d2 + d1 * statistic))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() < tolerance, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn normal_quantile_table_values() {
+        close(normal_quantile(0.5), 0.0, 1e-9);
+        close(normal_quantile(0.975), 1.959964, 1e-6);
+        close(normal_quantile(0.95), 1.644854, 1e-6);
+        close(normal_quantile(0.005), -2.575829, 1e-6);
+    }
+
+    #[test]
+    fn f_sf_table_values() {
+        // critical values of the F distribution
+        close(f_sf(4.3512, 1, 20), 0.05, 1e-4);
+        close(f_sf(3.4928, 2, 20), 0.05, 1e-4);
+        close(f_sf(3.7083, 3, 10), 0.05, 1e-4);
+        close(f_sf(8.0960, 1, 20), 0.01, 1e-4);
+        close(f_sf(0.0, 2, 20), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn chi_squared_sf_table_values() {
+        close(chi_squared_sf(3.8415, 1), 0.05, 1e-5);
+        close(chi_squared_sf(18.307, 10), 0.05, 1e-5);
+        close(chi_squared_sf(6.6349, 1), 0.01, 1e-5);
+        close(chi_squared_sf(2.0, 2), (-1.0f64).exp(), 1e-9);
+        close(chi_squared_sf(0.0, 3), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn least_squares_recovers_a_line() {
+        let rows: Vec<Vec<f64>> = (0..5).map(|x| vec![1.0, x as f64]).collect();
+        let y: Vec<f64> = (0..5).map(|x| 2.0 + 3.0 * x as f64).collect();
+        let coefficients = least_squares(&rows, &y).unwrap();
+        close(coefficients[0], 2.0, 1e-9);
+        close(coefficients[1], 3.0, 1e-9);
+    }
+}
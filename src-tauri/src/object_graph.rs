@@ -0,0 +1,508 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocdg::{Ocdg, Relations, NodeInfo, EdgeInfo};
+
+
+pub struct ObjectEdge {
+    pub source: usize,
+    pub target: usize,
+    // relation type -> number of events that support it
+    pub relations: BTreeMap<String, usize>
+}
+
+// A plain view of an OCDG: nodes are numbered like the node indices of `Ocdg.net` and relation
+// types are named like the Relations parameters of the plugins.
+pub struct ObjectGraph {
+    pub objects: Vec<String>,
+    pub object_types: Vec<String>,
+    pub edges: Vec<ObjectEdge>,
+    // only set for the diff of two OCDGs
+    pub status: Option<DiffStatus>
+}
+
+// Status of the objects and relations of a diff OCDG, keyed by object and by
+// (source, target, relation). Relations also keep their old and new weight.
+#[derive(Clone, Default)]
+pub struct DiffStatus {
+    pub nodes: HashMap<String, &'static str>,
+    pub edges: HashMap<(String, String, String), (&'static str, usize, usize)>
+}
+
+impl DiffStatus {
+    // The GEXF export of an OCDG has no place for the status, it is written next to it instead.
+    pub fn to_json(&self) -> Value {
+        let nodes: BTreeMap<&String, &str> = self.nodes.iter().map(|(object, status)| (object, *status)).collect();
+        let edges: BTreeMap<&(String, String, String), &(&str, usize, usize)> = self.edges.iter().collect();
+        json!({
+            "objects": nodes.iter().map(|(object, status)| json!({"object": object, "status": status})).collect::<Vec<Value>>(),
+            "relations": edges.iter().map(|((source, target, relation), (status, old, new))| {
+                json!({"source": source, "target": target, "relation": relation, "status": status, "old weight": old, "new weight": new})
+            }).collect::<Vec<Value>>()
+        })
+    }
+
+    // Entries with an unknown status are skipped.
+    pub fn from_json(value: &Value) -> DiffStatus {
+        let status = |entry: &Value| ["added", "removed", "changed", "unchanged"].iter().copied().find(|s| entry["status"].as_str() == Some(*s));
+        let text = |entry: &Value, key: &str| entry[key].as_str().map(|s| s.to_string());
+        let weight = |entry: &Value, key: &str| entry[key].as_u64().unwrap_or(0) as usize;
+        let entries = |key: &str| value[key].as_array().cloned().unwrap_or_default();
+        DiffStatus {
+            nodes: entries("objects").iter().filter_map(|n| Some((text(n, "object")?, status(n)?))).collect(),
+            edges: entries("relations").iter().filter_map(|e| {
+                Some(((text(e, "source")?, text(e, "target")?, text(e, "relation")?), (status(e)?, weight(e, "old weight"), weight(e, "new weight"))))
+            }).collect()
+        }
+    }
+}
+
+impl ObjectGraph {
+    pub fn from_ocdg(ocdg: &Ocdg) -> ObjectGraph {
+        let net = &ocdg.net;
+        let mut objects: Vec<String> = vec![String::new(); net.node_count()];
+        let mut object_types: Vec<String> = vec![String::new(); net.node_count()];
+        for node in net.node_indices() {
+            objects[node.index()] = ocdg.object_map.get_by_right(&net[node]).map_or_else(|| net[node].to_string(), |name| name.to_string());
+            object_types[node.index()] = ocdg.node_attributes.get(&node).map_or_else(String::new, |info| info.node_type.to_string());
+        }
+
+        let edges: Vec<ObjectEdge> = net.edge_indices().filter_map(|e| {
+            let (src, tar) = net.edge_endpoints(e)?;
+            let relations = ocdg.edge_attributes.get(&e).map_or_else(BTreeMap::new, |info| {
+                info.edge_type.iter().map(|(rel, events)| (format!("{:?}", rel), events.len())).collect()
+            });
+            Some(ObjectEdge { source: src.index(), target: tar.index(), relations })
+        }).collect();
+
+        ObjectGraph { objects, object_types, edges, status: None }
+    }
+
+    pub fn with_status(mut self, status: Option<&DiffStatus>) -> ObjectGraph {
+        self.status = status.cloned();
+        self
+    }
+
+    pub fn node_status(&self, node: usize) -> Option<&'static str> {
+        self.status.as_ref()?.nodes.get(&self.objects[node]).copied()
+    }
+
+    // status, old and new weight of one relation of an edge
+    pub fn relation_status(&self, edge: &ObjectEdge, relation: &str) -> Option<(&'static str, usize, usize)> {
+        let key = (self.objects[edge.source].to_string(), self.objects[edge.target].to_string(), relation.to_string());
+        self.status.as_ref()?.edges.get(&key).copied()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    // Undirected adjacency with both directions of an edge merged. The weight of an edge is the sum
+    // of the weights of its relation types (1 if a type has no weight), self loops are dropped.
+    pub fn undirected(&self, weights: &HashMap<String, f64>) -> Vec<BTreeMap<usize, f64>> {
+        let mut adjacency: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); self.node_count()];
+        for edge in self.edges.iter().filter(|e| e.source != e.target) {
+            let weight: f64 = if edge.relations.is_empty() { 1.0 } else { edge.relations.keys().map(|rel| weights.get(rel).copied().unwrap_or(1.0)).sum() };
+            if weight <= 0.0 {
+                continue;
+            }
+            *adjacency[edge.source].entry(edge.target).or_insert(0.0) += weight;
+            *adjacency[edge.target].entry(edge.source).or_insert(0.0) += weight;
+        }
+        adjacency
+    }
+}
+
+// parses "INTERACTS:2;COLIFE:0.5", relation types that are not listed keep a weight of 1
+pub fn parse_relation_weights(input: &str) -> Result<HashMap<String, f64>, String> {
+    input.split(';')
+         .map(|pair| pair.trim())
+         .filter(|pair| !pair.is_empty())
+         .map(|pair| {
+             let (rel, weight) = pair.split_once(':').ok_or(format!("Could not parse relation weight {}", pair))?;
+             let rel = Relations::from_str(rel.trim()).map_err(|_| format!("Unknown relation {}", rel))?;
+             let weight: f64 = weight.trim().parse().map_err(|_| format!("Could not parse weight {}", weight))?;
+             if weight < 0.0 {
+                 return Err(format!("The weight of {:?} has to be positive", rel));
+             }
+             Ok((format!("{:?}", rel), weight))
+         })
+         .collect()
+}
+
+// the relations of an edge and the events behind them
+type EdgeRelations = HashMap<Relations, Vec<usize>>;
+
+// Restrictions of an OCDG, all given restrictions have to hold. The time window needs the OCEL
+// the OCDG was generated from to look up the timestamps of the events behind the relations.
+#[derive(Default)]
+pub struct SubgraphFilter<'a> {
+    pub object_types: Option<HashSet<&'a str>>,
+    pub relations: Option<HashSet<Relations>>,
+    pub window: Option<(&'a Ocel, Option<DateTime<Utc>>, Option<DateTime<Utc>>)>,
+    pub neighbourhood: Option<(Vec<&'a str>, usize)>,
+    pub drop_isolated: bool
+}
+
+pub fn subgraph(ocdg: &Ocdg, filter: &SubgraphFilter) -> Result<Ocdg, String> {
+    let net = &ocdg.net;
+    let mut keep: Vec<bool> = net.node_indices().map(|n| {
+        filter.object_types.as_ref().map_or(true, |types| ocdg.node_attributes.get(&n).map_or(false, |info| types.contains(info.node_type.as_str())))
+    }).collect();
+
+    // the relations (and their events) that survive per edge
+    let restricted = filter.relations.is_some() || filter.window.is_some();
+    let mut relations: Vec<Option<EdgeRelations>> = net.edge_indices().map(|e| {
+        let (src, tar) = net.edge_endpoints(e)?;
+        if !keep[src.index()] || !keep[tar.index()] {
+            return None;
+        }
+        let kept: EdgeRelations = ocdg.edge_attributes.get(&e).map_or_else(HashMap::new, |info| {
+            info.edge_type.iter()
+                .filter(|(rel, _)| filter.relations.as_ref().map_or(true, |rels| rels.contains(*rel)))
+                .filter_map(|(rel, events)| match filter.window {
+                    Some((log, start, end)) => {
+                        let inside: Vec<usize> = events.iter().copied().filter(|eid| log.events.get(eid).map_or(false, |ev| start.map_or(true, |s| ev.timestamp >= s) && end.map_or(true, |e| ev.timestamp <= e))).collect();
+                        if inside.is_empty() { None } else { Some((*rel, inside)) }
+                    },
+                    None => Some((*rel, events.clone()))
+                })
+                .collect()
+        });
+        if restricted && kept.is_empty() { None } else { Some(kept) }
+    }).collect();
+
+    let adjacency = |keep: &[bool], relations: &[Option<EdgeRelations>]| -> Vec<Vec<usize>> {
+        let mut neighbours: Vec<Vec<usize>> = vec![vec![]; keep.len()];
+        net.edge_indices().filter(|e| relations[e.index()].is_some()).filter_map(|e| net.edge_endpoints(e)).for_each(|(src, tar)| {
+            if keep[src.index()] && keep[tar.index()] {
+                neighbours[src.index()].push(tar.index());
+                neighbours[tar.index()].push(src.index());
+            }
+        });
+        neighbours
+    };
+
+    if let Some((seeds, hops)) = &filter.neighbourhood {
+        let nodes: HashMap<usize, usize> = net.node_indices().map(|n| (net[n], n.index())).collect();
+        let mut distance: Vec<Option<usize>> = vec![None; keep.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for seed in seeds {
+            let node = ocdg.object_map.get_by_left(*seed).and_then(|oid| nodes.get(oid)).ok_or(format!("The object {} is not part of the OCDG", seed))?;
+            if keep[*node] && distance[*node].is_none() {
+                distance[*node] = Some(0);
+                queue.push_back(*node);
+            }
+        }
+        let neighbours = adjacency(&keep, &relations);
+        while let Some(v) = queue.pop_front() {
+            let d = distance[v].unwrap_or(0);
+            if d >= *hops {
+                continue;
+            }
+            for w in &neighbours[v] {
+                if distance[*w].is_none() {
+                    distance[*w] = Some(d + 1);
+                    queue.push_back(*w);
+                }
+            }
+        }
+        keep.iter_mut().zip(&distance).for_each(|(k, d)| *k = *k && d.is_some());
+    }
+
+    if filter.drop_isolated {
+        let neighbours = adjacency(&keep, &relations);
+        keep.iter_mut().zip(&neighbours).for_each(|(k, nb)| *k = *k && !nb.is_empty());
+    }
+
+    let mut kept_edges: Vec<usize> = vec![];
+    let new_net = net.filter_map(|n, oid| if keep[n.index()] { Some(*oid) } else { None },
+                                 |e, weight| if relations[e.index()].is_some() { kept_edges.push(e.index()); Some(*weight) } else { None });
+
+    let old_nodes: HashMap<usize, _> = net.node_indices().map(|n| (net[n], n)).collect();
+    let inodes: HashMap<usize, _> = new_net.node_indices().map(|n| (new_net[n], n)).collect();
+    let node_attributes = new_net.node_indices().filter_map(|n| {
+        ocdg.node_attributes.get(&old_nodes[&new_net[n]]).map(|info| (n, NodeInfo { node_type: info.node_type.to_string() }))
+    }).collect();
+    let edge_attributes = new_net.edge_indices().zip(kept_edges).map(|(e, old)| (e, EdgeInfo { edge_type: relations[old].take().unwrap_or_default() })).collect();
+    let mut object_map = ocdg.object_map.clone();
+    object_map.retain(|_, oid| inodes.contains_key(oid));
+
+    Ok(Ocdg { net: new_net, object_map, inodes, node_attributes, edge_attributes, ..Default::default() })
+}
+
+pub struct NodeChange {
+    pub object: String,
+    pub object_type: String,
+    pub status: &'static str
+}
+
+pub struct EdgeChange {
+    pub source: String,
+    pub target: String,
+    pub relation: String,
+    pub status: &'static str,
+    // number of events behind the relation
+    pub old_weight: usize,
+    pub new_weight: usize
+}
+
+fn status<T: PartialEq>(old: Option<T>, new: Option<T>) -> &'static str {
+    match (old, new) {
+        (None, _) => "added",
+        (_, None) => "removed",
+        (Some(o), Some(n)) if o != n => "changed",
+        _ => "unchanged"
+    }
+}
+
+// Objects are matched by their id and edges by their end points and relation type. Every
+// object and relation of either graph is reported once.
+pub fn diff(old: &ObjectGraph, new: &ObjectGraph) -> (Vec<NodeChange>, Vec<EdgeChange>) {
+    let nodes = |graph: &ObjectGraph| -> BTreeMap<String, String> {
+        graph.objects.iter().cloned().zip(graph.object_types.iter().cloned()).collect()
+    };
+    let edges = |graph: &ObjectGraph| -> BTreeMap<(String, String, String), usize> {
+        graph.edges.iter().flat_map(|e| {
+            e.relations.iter().map(move |(rel, events)| ((graph.objects[e.source].to_string(), graph.objects[e.target].to_string(), rel.to_string()), *events))
+        }).collect()
+    };
+
+    let (old_nodes, new_nodes) = (nodes(old), nodes(new));
+    let mut node_keys: Vec<&String> = old_nodes.keys().chain(new_nodes.keys()).collect();
+    node_keys.sort();
+    node_keys.dedup();
+    let node_changes = node_keys.into_iter().map(|object| NodeChange {
+        object: object.to_string(),
+        object_type: new_nodes.get(object).or_else(|| old_nodes.get(object)).cloned().unwrap_or_default(),
+        status: status(old_nodes.get(object), new_nodes.get(object))
+    }).collect();
+
+    let (old_edges, new_edges) = (edges(old), edges(new));
+    let mut edge_keys: Vec<&(String, String, String)> = old_edges.keys().chain(new_edges.keys()).collect();
+    edge_keys.sort();
+    edge_keys.dedup();
+    let edge_changes = edge_keys.into_iter().map(|key| EdgeChange {
+        source: key.0.to_string(),
+        target: key.1.to_string(),
+        relation: key.2.to_string(),
+        status: status(old_edges.get(key).copied(), new_edges.get(key).copied()),
+        old_weight: old_edges.get(key).copied().unwrap_or(0),
+        new_weight: new_edges.get(key).copied().unwrap_or(0)
+    }).collect();
+
+    (node_changes, edge_changes)
+}
+
+fn object_name(ocdg: &Ocdg, oid: usize) -> String {
+    ocdg.object_map.get_by_right(&oid).map_or_else(|| oid.to_string(), |name| name.to_string())
+}
+
+// The union of both OCDGs on top of `new`. Objects and relations that only occur in `old` are
+// added with the events of `old`. The changes of `diff` become the status of the union.
+pub fn union(old: &Ocdg, new: &Ocdg, nodes: &[NodeChange], edges: &[EdgeChange]) -> (Ocdg, DiffStatus) {
+    let mut union = Ocdg {
+        net: new.net.clone(),
+        object_map: new.object_map.clone(),
+        node_attributes: new.node_attributes.iter().map(|(n, info)| (*n, NodeInfo { node_type: info.node_type.to_string() })).collect(),
+        edge_attributes: new.edge_attributes.iter().map(|(e, info)| (*e, EdgeInfo { edge_type: info.edge_type.iter().map(|(rel, events)| (*rel, events.clone())).collect() })).collect(),
+        ..Default::default()
+    };
+
+    let mut index: HashMap<String, _> = union.net.node_indices().map(|n| (object_name(new, union.net[n]), n)).collect();
+    let mut next_oid = union.net.node_indices().map(|n| union.net[n] + 1).chain(new.object_map.iter().map(|(_, oid)| oid + 1)).max().unwrap_or(0);
+    for n in old.net.node_indices() {
+        let name = object_name(old, old.net[n]);
+        if index.contains_key(&name) {
+            continue;
+        }
+        let node = union.net.add_node(next_oid);
+        union.object_map.insert(name.to_string(), next_oid);
+        if let Some(info) = old.node_attributes.get(&n) {
+            union.node_attributes.insert(node, NodeInfo { node_type: info.node_type.to_string() });
+        }
+        index.insert(name, node);
+        next_oid += 1;
+    }
+
+    for e in old.net.edge_indices() {
+        let (src, tar) = match old.net.edge_endpoints(e) {
+            Some(ends) => ends,
+            None => continue
+        };
+        let (a, b) = (index[&object_name(old, old.net[src])], index[&object_name(old, old.net[tar])]);
+        let edge = match union.net.find_edge(a, b) {
+            Some(edge) => edge,
+            None => union.net.add_edge(a, b, old.net[e])
+        };
+        if let Some(info) = old.edge_attributes.get(&e) {
+            let relations = &mut union.edge_attributes.entry(edge).or_insert_with(|| EdgeInfo { edge_type: HashMap::new() }).edge_type;
+            for (rel, events) in &info.edge_type {
+                relations.entry(*rel).or_insert_with(|| events.clone());
+            }
+        }
+    }
+    union.inodes = union.net.node_indices().map(|n| (union.net[n], n)).collect();
+
+    let status = DiffStatus {
+        nodes: nodes.iter().map(|n| (n.object.to_string(), n.status)).collect(),
+        edges: edges.iter().map(|e| ((e.source.to_string(), e.target.to_string(), e.relation.to_string()), (e.status, e.old_weight, e.new_weight))).collect()
+    };
+    (union, status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+    use serde_json::json;
+    use crate::fixtures::{event, object, log, eid};
+
+    type TestEdge<'a> = (&'a str, &'a str, &'a [(&'a str, &'a [usize])]);
+
+    // objects are numbered in the given order, relations carry the ids of their events
+    fn ocdg(objects: &[(&str, &str)], edges: &[TestEdge]) -> Ocdg {
+        let mut ocdg = Ocdg::default();
+        let mut nodes = HashMap::new();
+        for (oid, (name, object_type)) in objects.iter().enumerate() {
+            let node = ocdg.net.add_node(oid);
+            ocdg.object_map.insert(name.to_string(), oid);
+            ocdg.inodes.insert(oid, node);
+            ocdg.node_attributes.insert(node, NodeInfo { node_type: object_type.to_string() });
+            nodes.insert(name.to_string(), node);
+        }
+        for (source, target, relations) in edges {
+            let edge = ocdg.net.add_edge(nodes[*source], nodes[*target], Default::default());
+            let edge_type = relations.iter().map(|(rel, events)| (Relations::from_str(rel).unwrap(), events.to_vec())).collect();
+            ocdg.edge_attributes.insert(edge, EdgeInfo { edge_type });
+        }
+        ocdg
+    }
+
+    fn objects(ocdg: &Ocdg) -> Vec<String> {
+        let mut objects = ObjectGraph::from_ocdg(ocdg).objects;
+        objects.sort();
+        objects
+    }
+
+    fn relations(ocdg: &Ocdg) -> Vec<(String, String, String, usize)> {
+        let graph = ObjectGraph::from_ocdg(ocdg);
+        let mut relations: Vec<(String, String, String, usize)> = graph.edges.iter().flat_map(|e| {
+            e.relations.iter().map(|(rel, events)| (graph.objects[e.source].to_string(), graph.objects[e.target].to_string(), rel.to_string(), *events)).collect::<Vec<_>>()
+        }).collect();
+        relations.sort();
+        relations
+    }
+
+    fn chain() -> Ocdg {
+        ocdg(&[("o1", "order"), ("i1", "item"), ("i2", "item"), ("d1", "delivery")],
+             &[("o1", "i1", &[("INTERACTS", &[0])]), ("i1", "i2", &[("COLIFE", &[1])]), ("i2", "d1", &[("INTERACTS", &[2])])])
+    }
+
+    #[test]
+    fn subgraph_time_window_keeps_the_events_inside() {
+        let log = log(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1", "i1"], json!({})),
+                           event("e2", "ship", "2022-01-03T10:00:00+00:00", &["i1", "i2"], json!({}))],
+                      vec![object("o1", "order", json!({})), object("i1", "item", json!({})), object("i2", "item", json!({}))]);
+        let (e1, e2) = (eid(&log, "e1"), eid(&log, "e2"));
+        let graph = ocdg(&[("o1", "order"), ("i1", "item"), ("i2", "item")],
+                         &[("o1", "i1", &[("INTERACTS", &[e1])]), ("i1", "i2", &[("INTERACTS", &[e2]), ("COLIFE", &[e1, e2])])]);
+        let start = Some(Utc.from_utc_datetime(&NaiveDate::from_ymd_opt(2022, 1, 2).unwrap().and_hms_opt(0, 0, 0).unwrap()));
+
+        let filter = SubgraphFilter { window: Some((&log, start, None)), ..Default::default() };
+        let sub = subgraph(&graph, &filter).unwrap();
+        assert_eq!(objects(&sub), vec!["i1", "i2", "o1"]);
+        assert_eq!(relations(&sub), vec![("i1".to_string(), "i2".to_string(), "COLIFE".to_string(), 1), ("i1".to_string(), "i2".to_string(), "INTERACTS".to_string(), 1)]);
+
+        let filter = SubgraphFilter { window: Some((&log, start, None)), drop_isolated: true, ..Default::default() };
+        assert_eq!(objects(&subgraph(&graph, &filter).unwrap()), vec!["i1", "i2"]);
+    }
+
+    #[test]
+    fn subgraph_k_hop_neighbourhood() {
+        let graph = chain();
+        let filter = SubgraphFilter { neighbourhood: Some((vec!["o1"], 2)), ..Default::default() };
+        let sub = subgraph(&graph, &filter).unwrap();
+        assert_eq!(objects(&sub), vec!["i1", "i2", "o1"]);
+        assert_eq!(relations(&sub).len(), 2);
+
+        // hops only follow the relations that are kept
+        let filter = SubgraphFilter { neighbourhood: Some((vec!["o1"], 3)), relations: Some(HashSet::from([Relations::from_str("INTERACTS").unwrap()])), ..Default::default() };
+        assert_eq!(objects(&subgraph(&graph, &filter).unwrap()), vec!["i1", "o1"]);
+
+        let filter = SubgraphFilter { neighbourhood: Some((vec!["x9"], 1)), ..Default::default() };
+        assert!(subgraph(&graph, &filter).is_err());
+    }
+
+    #[test]
+    fn subgraph_drops_isolated_objects() {
+        let graph = chain();
+        let filter = SubgraphFilter { object_types: Some(HashSet::from(["item", "delivery"])), drop_isolated: false, ..Default::default() };
+        assert_eq!(objects(&subgraph(&graph, &filter).unwrap()), vec!["d1", "i1", "i2"]);
+
+        let filter = SubgraphFilter { relations: Some(HashSet::from([Relations::from_str("COLIFE").unwrap()])), drop_isolated: true, ..Default::default() };
+        let sub = subgraph(&graph, &filter).unwrap();
+        assert_eq!(objects(&sub), vec!["i1", "i2"]);
+        assert_eq!(relations(&sub), vec![("i1".to_string(), "i2".to_string(), "COLIFE".to_string(), 1)]);
+    }
+
+    #[test]
+    fn diff_reports_every_object_and_relation_once() {
+        let old = ocdg(&[("o1", "order"), ("i1", "item"), ("i2", "item")],
+                       &[("o1", "i1", &[("INTERACTS", &[0, 1]), ("COLIFE", &[0])]), ("o1", "i2", &[("INTERACTS", &[2])])]);
+        let new = ocdg(&[("o1", "order"), ("i1", "item"), ("d1", "delivery")],
+                       &[("o1", "i1", &[("INTERACTS", &[0]), ("COLIFE", &[0])]), ("i1", "d1", &[("INTERACTS", &[3])])]);
+        let (nodes, edges) = diff(&ObjectGraph::from_ocdg(&old), &ObjectGraph::from_ocdg(&new));
+
+        let nodes: Vec<(&str, &str, &str)> = nodes.iter().map(|n| (n.object.as_str(), n.object_type.as_str(), n.status)).collect();
+        assert_eq!(nodes, vec![("d1", "delivery", "added"), ("i1", "item", "unchanged"), ("i2", "item", "removed"), ("o1", "order", "unchanged")]);
+        let edges: Vec<(&str, &str, &str, &str, usize, usize)> = edges.iter().map(|e| (e.source.as_str(), e.target.as_str(), e.relation.as_str(), e.status, e.old_weight, e.new_weight)).collect();
+        assert_eq!(edges, vec![("i1", "d1", "INTERACTS", "added", 0, 1),
+                               ("o1", "i1", "COLIFE", "unchanged", 1, 1),
+                               ("o1", "i1", "INTERACTS", "changed", 2, 1),
+                               ("o1", "i2", "INTERACTS", "removed", 1, 0)]);
+    }
+
+    #[test]
+    fn union_keeps_removed_objects_and_relations() {
+        let old = ocdg(&[("o1", "order"), ("i1", "item"), ("i2", "item")],
+                       &[("o1", "i1", &[("INTERACTS", &[0, 1])]), ("o1", "i2", &[("INTERACTS", &[2])])]);
+        // the ids of the new graph differ from the old one, objects are matched by name
+        let new = ocdg(&[("d1", "delivery"), ("i1", "item"), ("o1", "order")],
+                       &[("o1", "i1", &[("INTERACTS", &[0]), ("COLIFE", &[0])]), ("i1", "d1", &[("INTERACTS", &[3])])]);
+        let (nodes, edges) = diff(&ObjectGraph::from_ocdg(&old), &ObjectGraph::from_ocdg(&new));
+        let (union, status) = union(&old, &new, &nodes, &edges);
+
+        assert_eq!(objects(&union), vec!["d1", "i1", "i2", "o1"]);
+        // relations of both graphs, the new events win where a relation is in both
+        assert_eq!(relations(&union), vec![("i1".to_string(), "d1".to_string(), "INTERACTS".to_string(), 1),
+                                           ("o1".to_string(), "i1".to_string(), "COLIFE".to_string(), 1),
+                                           ("o1".to_string(), "i1".to_string(), "INTERACTS".to_string(), 1),
+                                           ("o1".to_string(), "i2".to_string(), "INTERACTS".to_string(), 1)]);
+        assert_eq!(union.inodes.len(), 4);
+        assert_eq!(union.object_map.len(), 4);
+
+        let graph = ObjectGraph::from_ocdg(&union).with_status(Some(&status));
+        let i2 = graph.objects.iter().position(|o| o == "i2").unwrap();
+        assert_eq!(graph.node_status(i2), Some("removed"));
+        assert_eq!(graph.object_types[i2], "item");
+        let changed = graph.edges.iter().find(|e| graph.objects[e.source] == "o1" && graph.objects[e.target] == "i1").unwrap();
+        assert_eq!(graph.relation_status(changed, "INTERACTS"), Some(("changed", 2, 1)));
+        assert_eq!(graph.relation_status(changed, "COLIFE"), Some(("added", 0, 1)));
+    }
+
+    #[test]
+    fn diff_status_round_trips_through_json() {
+        let old = ocdg(&[("o1", "order")], &[]);
+        let new = ocdg(&[("o1", "order"), ("i1", "item")], &[("o1", "i1", &[("INTERACTS", &[0])])]);
+        let (nodes, edges) = diff(&ObjectGraph::from_ocdg(&old), &ObjectGraph::from_ocdg(&new));
+        let (_, status) = union(&old, &new, &nodes, &edges);
+
+        let restored = DiffStatus::from_json(&status.to_json());
+        assert_eq!(restored.nodes, status.nodes);
+        assert_eq!(restored.edges, status.edges);
+        assert_eq!(restored.edges[&("o1".to_string(), "i1".to_string(), "INTERACTS".to_string())], ("added", 0, 1));
+    }
+}
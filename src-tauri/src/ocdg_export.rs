@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+use polars::prelude::{DataFrame, Series, NamedFrom};
+use serde_json::{Value, Map, json};
+use crate::object_graph::{ObjectEdge, ObjectGraph};
+
+
+// relation types that occur in the graph, every format gets one weight attribute per type
+fn relation_types(graph: &ObjectGraph) -> Vec<&str> {
+    graph.edges.iter().flat_map(|e| e.relations.keys().map(|rel| rel.as_str())).collect::<BTreeSet<&str>>().into_iter().collect()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn to_graphml(graph: &ObjectGraph) -> String {
+    let relations = relation_types(graph);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    xml.push_str("  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n");
+    xml.push_str("  <key id=\"relations\" for=\"edge\" attr.name=\"relations\" attr.type=\"string\"/>\n");
+    for (i, rel) in relations.iter().enumerate() {
+        xml.push_str(&format!("  <key id=\"r{}\" for=\"edge\" attr.name=\"{}\" attr.type=\"int\"><default>0</default></key>\n", i, xml_escape(rel)));
+    }
+    // a diff OCDG also has the status of every object and the status, old and new weight of every relation
+    if graph.status.is_some() {
+        xml.push_str("  <key id=\"status\" for=\"node\" attr.name=\"status\" attr.type=\"string\"/>\n");
+        for (i, rel) in relations.iter().enumerate() {
+            xml.push_str(&format!("  <key id=\"s{}\" for=\"edge\" attr.name=\"{} status\" attr.type=\"string\"/>\n", i, xml_escape(rel)));
+            xml.push_str(&format!("  <key id=\"o{}\" for=\"edge\" attr.name=\"{} old\" attr.type=\"int\"><default>0</default></key>\n", i, xml_escape(rel)));
+            xml.push_str(&format!("  <key id=\"n{}\" for=\"edge\" attr.name=\"{} new\" attr.type=\"int\"><default>0</default></key>\n", i, xml_escape(rel)));
+        }
+    }
+
+    xml.push_str("  <graph id=\"ocdg\" edgedefault=\"directed\">\n");
+    for (n, (object, object_type)) in graph.objects.iter().zip(&graph.object_types).enumerate() {
+        let status = graph.node_status(n).map_or_else(String::new, |status| format!("<data key=\"status\">{}</data>", status));
+        xml.push_str(&format!("    <node id=\"{}\"><data key=\"type\">{}</data>{}</node>\n", xml_escape(object), xml_escape(object_type), status));
+    }
+    for (e, edge) in graph.edges.iter().enumerate() {
+        xml.push_str(&format!("    <edge id=\"e{}\" source=\"{}\" target=\"{}\">", e, xml_escape(&graph.objects[edge.source]), xml_escape(&graph.objects[edge.target])));
+        xml.push_str(&format!("<data key=\"relations\">{}</data>", xml_escape(&edge.relations.keys().cloned().collect::<Vec<String>>().join(";"))));
+        for (i, rel) in relations.iter().enumerate() {
+            if let Some(weight) = edge.relations.get(*rel) {
+                xml.push_str(&format!("<data key=\"r{}\">{}</data>", i, weight));
+            }
+            if let Some((status, old, new)) = graph.relation_status(edge, rel) {
+                xml.push_str(&format!("<data key=\"s{}\">{}</data><data key=\"o{}\">{}</data><data key=\"n{}\">{}</data>", i, status, i, old, i, new));
+            }
+        }
+        xml.push_str("</edge>\n");
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+fn status_color(status: &str) -> &'static str {
+    match status {
+        "added" => "darkgreen",
+        "removed" => "red",
+        "changed" => "orange",
+        _ => "black"
+    }
+}
+
+pub fn to_dot(graph: &ObjectGraph) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut dot = String::from("digraph ocdg {\n  node [shape=ellipse];\n");
+    for (i, (object, object_type)) in graph.objects.iter().zip(&graph.object_types).enumerate() {
+        let status = graph.node_status(i).map_or_else(String::new, |status| format!(", status=\"{}\", color=\"{}\"", status, status_color(status)));
+        dot.push_str(&format!("  n{} [label=\"{}\", type=\"{}\"{}];\n", i, escape(object), escape(object_type), status));
+    }
+    for edge in &graph.edges {
+        let label = edge.relations.iter().map(|(rel, weight)| match graph.relation_status(edge, rel) {
+            Some((status, old, new)) => escape(&format!("{} ({} -> {}, {})", rel, old, new, status)),
+            None => escape(&format!("{} ({})", rel, weight))
+        }).collect::<Vec<String>>().join("\\n");
+        dot.push_str(&format!("  n{} -> n{} [label=\"{}\"];\n", edge.source, edge.target, label));
+    }
+    dot.push('}');
+    dot
+}
+
+// the elements format that cytoscape.js reads with cy.add / cy.json
+pub fn to_cytoscape(graph: &ObjectGraph) -> Value {
+    let nodes: Vec<Value> = graph.objects.iter().zip(&graph.object_types).enumerate().map(|(n, (object, object_type))| {
+        let mut data = Map::from_iter([("id".to_string(), json!(object)), ("label".to_string(), json!(object)), ("type".to_string(), json!(object_type))]);
+        if let Some(status) = graph.node_status(n) {
+            data.insert("status".to_string(), json!(status));
+        }
+        json!({"data": data})
+    }).collect();
+    let edges: Vec<Value> = graph.edges.iter().enumerate().map(|(e, edge)| {
+        let mut data = Map::from_iter([("id".to_string(), json!(format!("e{}", e))),
+                                       ("source".to_string(), json!(graph.objects[edge.source])),
+                                       ("target".to_string(), json!(graph.objects[edge.target])),
+                                       ("relations".to_string(), json!(edge.relations.keys().collect::<Vec<&String>>()))]);
+        edge.relations.iter().for_each(|(rel, weight)| {data.insert(rel.to_string(), json!(weight));});
+        edge.relations.keys().filter_map(|rel| graph.relation_status(edge, rel).map(|s| (rel, s))).for_each(|(rel, (status, old, new))| {
+            data.insert(format!("{} status", rel), json!(status));
+            data.insert(format!("{} old", rel), json!(old));
+            data.insert(format!("{} new", rel), json!(new));
+        });
+        json!({"data": data})
+    }).collect();
+    json!({"elements": {"nodes": nodes, "edges": edges}})
+}
+
+// one row per edge with the types of both objects and one weight column per relation type.
+// Objects without any edge are kept as rows without a target. As CSV it is written with the
+// '|' delimiter of the table export.
+pub fn edge_list(graph: &ObjectGraph) -> DataFrame {
+    let relations = relation_types(graph);
+    let mut connected: Vec<bool> = vec![false; graph.node_count()];
+    graph.edges.iter().for_each(|e| {connected[e.source] = true; connected[e.target] = true;});
+    let isolated: Vec<usize> = (0..graph.node_count()).filter(|n| !connected[*n]).collect();
+
+    let mut series: Vec<Series> = vec![
+        Series::new("Source", graph.edges.iter().map(|e| Some(graph.objects[e.source].as_str())).chain(isolated.iter().map(|n| Some(graph.objects[*n].as_str()))).collect::<Vec<Option<&str>>>()),
+        Series::new("Target", graph.edges.iter().map(|e| Some(graph.objects[e.target].as_str())).chain(isolated.iter().map(|_| None)).collect::<Vec<Option<&str>>>()),
+        Series::new("Source Type", graph.edges.iter().map(|e| Some(graph.object_types[e.source].as_str())).chain(isolated.iter().map(|n| Some(graph.object_types[*n].as_str()))).collect::<Vec<Option<&str>>>()),
+        Series::new("Target Type", graph.edges.iter().map(|e| Some(graph.object_types[e.target].as_str())).chain(isolated.iter().map(|_| None)).collect::<Vec<Option<&str>>>()),
+        Series::new("Relations", graph.edges.iter().map(|e| Some(e.relations.keys().cloned().collect::<Vec<String>>().join(";"))).chain(isolated.iter().map(|_| None)).collect::<Vec<Option<String>>>())
+    ];
+    for rel in &relations {
+        series.push(Series::new(rel, graph.edges.iter().map(|e| e.relations.get(*rel).copied().unwrap_or(0) as u32).chain(isolated.iter().map(|_| 0)).collect::<Vec<u32>>()));
+    }
+    if graph.status.is_some() {
+        series.push(Series::new("Source Status", graph.edges.iter().map(|e| e.source).chain(isolated.iter().copied()).map(|n| graph.node_status(n)).collect::<Vec<Option<&str>>>()));
+        series.push(Series::new("Target Status", graph.edges.iter().map(|e| graph.node_status(e.target)).chain(isolated.iter().map(|_| None)).collect::<Vec<Option<&str>>>()));
+        for rel in &relations {
+            let status = |e: &ObjectEdge| graph.relation_status(e, rel);
+            series.push(Series::new(&format!("{} Status", rel), graph.edges.iter().map(|e| status(e).map(|s| s.0)).chain(isolated.iter().map(|_| None)).collect::<Vec<Option<&str>>>()));
+            series.push(Series::new(&format!("{} Old", rel), graph.edges.iter().map(|e| status(e).map_or(0, |s| s.1) as u32).chain(isolated.iter().map(|_| 0)).collect::<Vec<u32>>()));
+            series.push(Series::new(&format!("{} New", rel), graph.edges.iter().map(|e| status(e).map_or(0, |s| s.2) as u32).chain(isolated.iter().map(|_| 0)).collect::<Vec<u32>>()));
+        }
+    }
+    DataFrame::new(series).expect("Data Table Creation went wrong")
+}
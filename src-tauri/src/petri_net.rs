@@ -0,0 +1,300 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use pmrs::objects::ocel::Ocel;
+use serde::{Serialize, Deserialize};
+use crate::inductive::{ProcessTree, SimpleDfg, discover_process_tree};
+use crate::dfg::{escape, PALETTE};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Place {
+    pub id: String,
+    pub object_type: String,
+    pub initial: bool,
+    pub final_place: bool
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    pub id: String,
+    // silent transitions have no label
+    pub label: Option<String>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Arc {
+    pub source: String,
+    pub target: String,
+    pub object_type: String,
+    // variable arcs consume / produce any number of tokens of the object type at once
+    pub variable: bool
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcPetriNet {
+    pub places: Vec<Place>,
+    pub transitions: Vec<Transition>,
+    pub arcs: Vec<Arc>
+}
+
+impl OcPetriNet {
+    pub fn object_types(&self) -> Vec<&str> {
+        let mut otypes: Vec<&str> = self.places.iter().map(|p| p.object_type.as_str()).collect::<HashSet<&str>>().into_iter().collect();
+        otypes.sort_unstable();
+        otypes
+    }
+
+    fn add_place(&mut self, object_type: &str) -> String {
+        let id = format!("p{}", self.places.len());
+        self.places.push(Place { id: id.to_string(), object_type: object_type.to_string(), initial: false, final_place: false });
+        id
+    }
+
+    fn add_silent(&mut self) -> String {
+        let id = format!("tau{}", self.transitions.len());
+        self.transitions.push(Transition { id: id.to_string(), label: None });
+        id
+    }
+
+    // visible transitions are shared by all object types
+    fn get_or_add_visible(&mut self, label: &str) -> String {
+        let id = format!("t:{}", label);
+        if !self.transitions.iter().any(|t| t.id == id) {
+            self.transitions.push(Transition { id: id.to_string(), label: Some(label.to_string()) });
+        }
+        id
+    }
+
+    fn add_arc(&mut self, source: &str, target: &str, object_type: &str) {
+        self.arcs.push(Arc { source: source.to_string(), target: target.to_string(), object_type: object_type.to_string(), variable: false });
+    }
+
+    fn connect(&mut self, transition: &str, input: &str, output: &str, object_type: &str) {
+        self.add_arc(input, transition, object_type);
+        self.add_arc(transition, output, object_type);
+    }
+
+    // translate a process tree into the net between the places `input` and `output`
+    fn add_tree(&mut self, tree: &ProcessTree, input: &str, output: &str, object_type: &str) {
+        match tree {
+            ProcessTree::Activity(act) => {
+                let t = self.get_or_add_visible(act);
+                self.connect(&t, input, output, object_type);
+            },
+            ProcessTree::Tau => {
+                let t = self.add_silent();
+                self.connect(&t, input, output, object_type);
+            },
+            ProcessTree::Sequence(children) => {
+                let mut curr = input.to_string();
+                for (i, child) in children.iter().enumerate() {
+                    let next = if i + 1 == children.len() { output.to_string() } else { self.add_place(object_type) };
+                    self.add_tree(child, &curr, &next, object_type);
+                    curr = next;
+                }
+            },
+            ProcessTree::Xor(children) => {
+                children.iter().for_each(|child| self.add_tree(child, input, output, object_type));
+            },
+            ProcessTree::Parallel(children) => {
+                let split = self.add_silent();
+                let join = self.add_silent();
+                self.add_arc(input, &split, object_type);
+                self.add_arc(&join, output, object_type);
+                for child in children {
+                    let (child_in, child_out) = (self.add_place(object_type), self.add_place(object_type));
+                    self.add_arc(&split, &child_in, object_type);
+                    self.add_arc(&child_out, &join, object_type);
+                    self.add_tree(child, &child_in, &child_out, object_type);
+                }
+            },
+            ProcessTree::Loop(children) => {
+                let (loop_in, loop_out) = (self.add_place(object_type), self.add_place(object_type));
+                let enter = self.add_silent();
+                let exit = self.add_silent();
+                self.connect(&enter, input, &loop_in, object_type);
+                self.connect(&exit, &loop_out, output, object_type);
+                self.add_tree(&children[0], &loop_in, &loop_out, object_type);
+                children[1..].iter().for_each(|redo| self.add_tree(redo, &loop_out, &loop_in, object_type));
+            }
+        }
+    }
+
+    pub fn to_pnml(&self) -> String {
+        let mut pnml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<pnml>\n  <net id=\"ocpn\" type=\"http://www.pnml.org/version-2009/grammar/pnmlcoremodel\">\n    <page id=\"page0\">\n");
+        for p in &self.places {
+            pnml.push_str(&format!("      <place id=\"{}\">\n        <name><text>{}</text></name>\n", xml_escape(&p.id), xml_escape(&p.id)));
+            if p.initial {
+                pnml.push_str("        <initialMarking><text>1</text></initialMarking>\n");
+            }
+            pnml.push_str(&format!("        <toolspecific tool=\"process-tool\" version=\"0.1\"><objectType>{}</objectType><final>{}</final></toolspecific>\n      </place>\n", xml_escape(&p.object_type), p.final_place));
+        }
+        for t in &self.transitions {
+            pnml.push_str(&format!("      <transition id=\"{}\">\n", xml_escape(&t.id)));
+            match &t.label {
+                Some(label) => pnml.push_str(&format!("        <name><text>{}</text></name>\n", xml_escape(label))),
+                None => pnml.push_str(&format!("        <name><text>{}</text></name>\n        <toolspecific tool=\"ProM\" version=\"6.4\" activity=\"$invisible$\" localNodeID=\"{}\"/>\n", xml_escape(&t.id), xml_escape(&t.id)))
+            }
+            pnml.push_str("      </transition>\n");
+        }
+        for (i, a) in self.arcs.iter().enumerate() {
+            pnml.push_str(&format!("      <arc id=\"a{}\" source=\"{}\" target=\"{}\">\n        <toolspecific tool=\"process-tool\" version=\"0.1\"><objectType>{}</objectType><variable>{}</variable></toolspecific>\n      </arc>\n",
+                                   i, xml_escape(&a.source), xml_escape(&a.target), xml_escape(&a.object_type), a.variable));
+        }
+        pnml.push_str("    </page>\n    <finalmarkings>\n      <marking>\n");
+        for p in self.places.iter().filter(|p| p.final_place) {
+            pnml.push_str(&format!("        <place idref=\"{}\"><text>1</text></place>\n", xml_escape(&p.id)));
+        }
+        pnml.push_str("      </marking>\n    </finalmarkings>\n  </net>\n</pnml>\n");
+        pnml
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph ocpn {\n    rankdir=LR;\n");
+        let colours: HashMap<&str, &str> = self.object_types().into_iter().enumerate().map(|(i, ot)| (ot, PALETTE[i % PALETTE.len()])).collect();
+
+        for p in &self.places {
+            dot.push_str(&format!("    \"{}\" [shape=circle, label=\"\", style=filled, fillcolor=\"{}\"];\n", escape(&p.id), colours[p.object_type.as_str()]));
+        }
+        for t in &self.transitions {
+            match &t.label {
+                Some(label) => dot.push_str(&format!("    \"{}\" [shape=box, label=\"{}\"];\n", escape(&t.id), escape(label))),
+                None => dot.push_str(&format!("    \"{}\" [shape=box, label=\"\", style=filled, fillcolor=black, width=0.2];\n", escape(&t.id)))
+            }
+        }
+        for a in &self.arcs {
+            dot.push_str(&format!("    \"{}\" -> \"{}\" [color=\"{}\"{}];\n", escape(&a.source), escape(&a.target), colours[a.object_type.as_str()], if a.variable { ", penwidth=3" } else { "" }));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+pub fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+// (object id, trace) of every object of the given type, traces are ordered by timestamp
+pub fn flatten_log<'a>(log: &'a Ocel, object_type: &str) -> Vec<(usize, Vec<&'a str>)> {
+    let mut traces: Vec<(usize, Vec<&str>)> = log.objects.iter()
+                                                         .filter(|(_, obj)| obj.obj_type == object_type)
+                                                         .map(|(oid, obj)| {
+                                                             let mut trace: Vec<usize> = obj.events.iter().filter(|eid| log.events.contains_key(eid)).copied().collect();
+                                                             trace.sort_by_key(|eid| (log.events[eid].timestamp, *eid));
+                                                             (*oid, trace.iter().map(|eid| log.events[eid].activity.as_str()).collect())
+                                                         })
+                                                         .collect();
+    traces.sort_unstable_by_key(|(oid, _)| *oid);
+    traces
+}
+
+// Discover one process tree per object type with the inductive miner and merge the resulting
+// nets on their shared activities.
+pub fn discover_ocpn(log: &Ocel, object_types: &[String]) -> (OcPetriNet, BTreeMap<String, ProcessTree>) {
+    let mut net = OcPetriNet::default();
+    let mut trees: BTreeMap<String, ProcessTree> = BTreeMap::new();
+
+    for ot in object_types {
+        let traces: Vec<Vec<&str>> = flatten_log(log, ot).into_iter().map(|(_, trace)| trace).collect();
+        if traces.is_empty() {
+            continue;
+        }
+        let tree = discover_process_tree(&SimpleDfg::from_traces(&traces));
+        let source = net.add_place(ot);
+        let sink = net.add_place(ot);
+        net.places.iter_mut().for_each(|p| {
+            if p.id == source { p.initial = true; }
+            if p.id == sink { p.final_place = true; }
+        });
+        net.add_tree(&tree, &source, &sink, ot);
+        trees.insert(ot.to_string(), tree);
+    }
+
+    // an arc is variable if some event of the activity involves more than one object of the type
+    let mut variable: HashSet<(&str, &str)> = HashSet::new();
+    for ev in log.events.values() {
+        let mut per_type: HashMap<&str, usize> = HashMap::new();
+        ev.omap.iter().filter_map(|oid| log.objects.get(oid)).for_each(|obj| *per_type.entry(obj.obj_type.as_str()).or_insert(0) += 1);
+        per_type.into_iter().filter(|(_, count)| *count > 1).for_each(|(ot, _)| {variable.insert((ev.activity.as_str(), ot));});
+    }
+
+    let labels: HashMap<String, String> = net.transitions.iter().filter_map(|t| t.label.as_ref().map(|l| (t.id.to_string(), l.to_string()))).collect();
+    net.arcs.iter_mut().for_each(|arc| {
+        let transition = if labels.contains_key(&arc.source) { &arc.source } else { &arc.target };
+        if let Some(label) = labels.get(transition) {
+            arc.variable = variable.contains(&(label.as_str(), arc.object_type.as_str()));
+        }
+    });
+
+    (net, trees)
+}
+
+pub fn tree_to_string(tree: &ProcessTree) -> String {
+    let join = |children: &Vec<ProcessTree>| children.iter().map(tree_to_string).collect::<Vec<String>>().join(", ");
+    match tree {
+        ProcessTree::Activity(act) => format!("'{}'", act),
+        ProcessTree::Tau => "tau".to_string(),
+        ProcessTree::Sequence(children) => format!("->({})", join(children)),
+        ProcessTree::Xor(children) => format!("X({})", join(children)),
+        ProcessTree::Parallel(children) => format!("+({})", join(children)),
+        ProcessTree::Loop(children) => format!("*({})", join(children))
+    }
+}
+
+fn child_text<'a>(node: &roxmltree::Node<'a, '_>, path: &[&str]) -> Option<&'a str> {
+    let mut curr = *node;
+    for tag in path {
+        curr = curr.children().find(|c| c.has_tag_name(*tag))?;
+    }
+    curr.text().map(|t| t.trim())
+}
+
+fn process_tool_annotation<'a>(node: &roxmltree::Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    node.children()
+        .filter(|c| c.has_tag_name("toolspecific"))
+        .find_map(|ts| ts.children().find(|c| c.has_tag_name(tag)).and_then(|c| c.text()).map(|t| t.trim()))
+}
+
+// Import a PNML file with the object type extensions written by `OcPetriNet::to_pnml`.
+// Every place needs an object type annotation, arcs without one inherit the type of their place.
+pub fn import_pnml(path: &str) -> Result<OcPetriNet, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let doc = roxmltree::Document::parse(&content).map_err(|e| e.to_string())?;
+    let mut net = OcPetriNet::default();
+
+    let final_places: HashSet<&str> = doc.descendants()
+                                         .filter(|n| n.has_tag_name("finalmarkings"))
+                                         .flat_map(|n| n.descendants().filter(|p| p.has_tag_name("place")).filter_map(|p| p.attribute("idref")))
+                                         .collect();
+
+    for (node, id) in doc.descendants().filter(|n| n.has_tag_name("place")).filter_map(|n| n.attribute("id").map(|id| (n, id))) {
+        let object_type = process_tool_annotation(&node, "objectType").ok_or(format!("Place {} has no object type annotation", id))?;
+        let initial = child_text(&node, &["initialMarking", "text"]).map_or(false, |m| m.parse::<u32>().unwrap_or(0) > 0);
+        let final_place = final_places.contains(id) || process_tool_annotation(&node, "final").map_or(false, |f| f == "true");
+        net.places.push(Place { id: id.to_string(), object_type: object_type.to_string(), initial, final_place });
+    }
+
+    for node in doc.descendants().filter(|n| n.has_tag_name("transition")) {
+        let id = node.attribute("id").ok_or("Transition without id")?;
+        let invisible = node.children().any(|c| c.has_tag_name("toolspecific") && c.attribute("activity") == Some("$invisible$"));
+        let label = if invisible { None } else { child_text(&node, &["name", "text"]).map(|l| l.to_string()) };
+        net.transitions.push(Transition { id: id.to_string(), label });
+    }
+
+    let place_types: HashMap<String, String> = net.places.iter().map(|p| (p.id.to_string(), p.object_type.to_string())).collect();
+    for node in doc.descendants().filter(|n| n.has_tag_name("arc")) {
+        let source = node.attribute("source").ok_or("Arc without source")?;
+        let target = node.attribute("target").ok_or("Arc without target")?;
+        let object_type = match process_tool_annotation(&node, "objectType") {
+            Some(ot) => ot.to_string(),
+            None => place_types.get(source).or_else(|| place_types.get(target)).ok_or(format!("Arc {} -> {} is not connected to a place", source, target))?.to_string()
+        };
+        let variable = process_tool_annotation(&node, "variable").map_or(false, |v| v == "true");
+        net.arcs.push(Arc { source: source.to_string(), target: target.to_string(), object_type, variable });
+    }
+
+    if net.places.is_empty() {
+        return Err("The PNML file does not contain any places".to_string());
+    }
+
+    Ok(net)
+}
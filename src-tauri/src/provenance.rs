@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use polars::prelude::{DataFrame, Series};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::tables::{id_column, align_series};
+
+
+// where a single table column came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnProvenance {
+    pub name: String,
+    // feature family (ObjectPoint, EventGroup, ...) or the plugin that created the column
+    pub family: String,
+    pub variant: Option<String>,
+    pub params: Option<Value>,
+    // rust ids of the entities the column was computed from
+    pub sources: Vec<usize>,
+    pub dtype: String
+}
+
+// provenance for tables that are not built from feature parameters, every column only
+// records the family and the sources
+pub fn table_provenance(df: &DataFrame, family: &str, sources: &[usize]) -> Vec<ColumnProvenance> {
+    df.get_columns().iter().map(|series| ColumnProvenance {
+        name: series.name().to_string(),
+        family: family.to_string(),
+        variant: None,
+        params: None,
+        sources: sources.to_vec(),
+        dtype: series.dtype().to_string()
+    }).collect()
+}
+
+// Provenance of a log after the columns of a table were merged into its objects. The first table
+// column holds the object ids, attributes the log already has keep their provenance (and values).
+pub fn merge_provenance(attributes: &[ColumnProvenance], table_columns: &[ColumnProvenance]) -> Vec<ColumnProvenance> {
+    let mut merged = attributes.to_vec();
+    for column in table_columns.iter().skip(1) {
+        if !merged.iter().any(|attr| attr.name == column.name) {
+            merged.push(column.clone());
+        }
+    }
+    merged
+}
+
+// Build a feature table one parameter at a time so that every column is recorded with the
+// variant and parameter value that generated it. The first column holds the object/event ids,
+// the columns of every part are aligned on the ids of the first part.
+pub fn build_features<T, F>(params: Vec<(T, Option<Value>)>, family: &str, sources: &[usize], build: F) -> Result<(DataFrame, Vec<ColumnProvenance>), String>
+    where T: Debug, F: Fn(&Vec<(T, Option<Value>)>) -> DataFrame {
+    if params.is_empty() {
+        let df = build(&params);
+        let columns = table_provenance(&df, family, sources);
+        return Ok((df, columns));
+    }
+
+    let mut series: Vec<Series> = vec![];
+    let mut columns: Vec<ColumnProvenance> = vec![];
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut ids: Vec<Option<String>> = vec![];
+    for (i, param) in params.into_iter().enumerate() {
+        let (variant, value) = (format!("{:?}", param.0), param.1.clone());
+        let part = build(&vec![param]);
+        let cols = part.get_columns();
+        if cols.is_empty() {
+            continue;
+        }
+        let part_ids = id_column(&part)?;
+        if series.is_empty() {
+            columns.push(ColumnProvenance {
+                name: cols[0].name().to_string(),
+                family: "Identifier".to_string(),
+                variant: None,
+                params: None,
+                sources: sources.to_vec(),
+                dtype: cols[0].dtype().to_string()
+            });
+            series.push(cols[0].clone());
+            taken.insert(cols[0].name().to_string());
+            ids = part_ids.clone();
+        }
+
+        let rows: Option<Vec<Option<usize>>> = if part_ids == ids {
+            None
+        } else {
+            let part_index: HashMap<&String, usize> = part_ids.iter().enumerate().filter_map(|(row, id)| id.as_ref().map(|id| (id, row))).collect();
+            Some(ids.iter().map(|id| id.as_ref().and_then(|id| part_index.get(id)).copied()).collect())
+        };
+        for col in cols.iter().skip(1) {
+            let mut name = col.name().to_string();
+            if taken.contains(&name) {
+                name = format!("{}:{}", name, i);
+            }
+            let aligned = match &rows {
+                Some(rows) => align_series(col, rows, name.as_str()),
+                None => {
+                    let mut col = col.clone();
+                    col.rename(name.as_str());
+                    col
+                }
+            };
+            taken.insert(name.to_string());
+            columns.push(ColumnProvenance {
+                name,
+                family: family.to_string(),
+                variant: Some(variant.to_string()),
+                params: value.clone(),
+                sources: sources.to_vec(),
+                dtype: aligned.dtype().to_string()
+            });
+            series.push(aligned);
+        }
+    }
+
+    // every part covers the same objects/events, ids only later parts know about are not added
+    let df = DataFrame::new(series).map_err(|e| e.to_string())?;
+    Ok((df, columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::NamedFrom;
+    use serde_json::json;
+
+    fn column(name: &str, family: &str, sources: &[usize]) -> ColumnProvenance {
+        ColumnProvenance { name: name.to_string(), family: family.to_string(), variant: None, params: None, sources: sources.to_vec(), dtype: "f64".to_string() }
+    }
+
+    #[test]
+    fn table_provenance_records_every_column() {
+        let df = DataFrame::new(vec![Series::new("Object", &["o1", "o2"]), Series::new("Score", &[0.5, 1.5])]).unwrap();
+        let columns = table_provenance(&df, "Clustering", &[3, 7]);
+        assert_eq!(columns.iter().map(|c| c.name.as_str()).collect::<Vec<&str>>(), vec!["Object", "Score"]);
+        assert!(columns.iter().all(|c| c.family == "Clustering" && c.sources == vec![3, 7] && c.variant.is_none() && c.params.is_none()));
+        assert_eq!(columns[1].dtype, df.column("Score").unwrap().dtype().to_string());
+    }
+
+    #[test]
+    fn merge_keeps_existing_attribute_provenance() {
+        let mut feature = column("lifetime", "ObjectPoint", &[1, 2]);
+        feature.variant = Some("ObjectLifetime".to_string());
+        feature.params = Some(json!({"unit": "s"}));
+        let log_attributes = vec![column("score", "Clustering", &[5])];
+        let table_columns = vec![column("Object", "Identifier", &[1, 2]), feature, column("score", "AnomalyDetection", &[9])];
+
+        let merged = merge_provenance(&log_attributes, &table_columns);
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].name.as_str(), merged[0].family.as_str()), ("score", "Clustering"));
+        assert_eq!(merged[1].name, "lifetime");
+        assert_eq!(merged[1].variant.as_deref(), Some("ObjectLifetime"));
+        assert_eq!(merged[1].params, Some(json!({"unit": "s"})));
+        assert_eq!(merged[1].sources, vec![1, 2]);
+    }
+}
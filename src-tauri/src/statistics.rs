@@ -0,0 +1,214 @@
+use std::collections::{HashMap, BTreeMap, HashSet};
+use pmrs::objects::ocel::Ocel;
+use polars::prelude::{DataFrame, Series, NamedFrom};
+use serde_json::{Value, json};
+
+
+// every table the describe plugin produces, in the order they are stored
+pub struct OcelDescription {
+    pub activities: DataFrame,
+    pub object_types: DataFrame,
+    pub events_per_object: DataFrame,
+    pub objects_per_event: DataFrame,
+    pub attribute_completeness: DataFrame,
+    pub activity_object_types: DataFrame,
+    pub summary: Vec<(String, Value)>
+}
+
+pub fn get_object_types(log: &Ocel) -> Vec<String> {
+    match log.global_log.get("ocel:object-types") {
+        Some(Value::Array(otypes)) => otypes.iter().filter_map(|ot| ot.as_str().map(|s| s.to_string())).collect(),
+        _ => {
+            let mut otypes: Vec<String> = log.objects.values().map(|obj| obj.obj_type.to_string()).collect::<HashSet<String>>().into_iter().collect();
+            otypes.sort();
+            otypes
+        }
+    }
+}
+
+fn distribution_table(counts: impl Iterator<Item = usize>, count_name: &str, total_name: &str) -> DataFrame {
+    let mut distribution: BTreeMap<usize, u32> = BTreeMap::new();
+    counts.for_each(|c| *distribution.entry(c).or_insert(0) += 1);
+
+    let keys: Vec<u32> = distribution.keys().map(|k| *k as u32).collect();
+    let values: Vec<u32> = distribution.values().copied().collect();
+    DataFrame::new(vec![Series::new(count_name, keys), Series::new(total_name, values)]).expect("Data Table Creation went wrong")
+}
+
+fn activity_frequencies(log: &Ocel) -> DataFrame {
+    let mut freq: HashMap<&str, u32> = log.activities.iter().map(|act| (act.as_str(), 0)).collect();
+    log.events.values().for_each(|ev| *freq.entry(ev.activity.as_str()).or_insert(0) += 1);
+    let total = log.events.len().max(1) as f64;
+
+    let mut rows: Vec<(&str, u32)> = freq.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    DataFrame::new(vec![Series::new("Activity", rows.iter().map(|r| r.0).collect::<Vec<&str>>()),
+                        Series::new("Frequency", rows.iter().map(|r| r.1).collect::<Vec<u32>>()),
+                        Series::new("Relative Frequency", rows.iter().map(|r| r.1 as f64 / total).collect::<Vec<f64>>())]).expect("Data Table Creation went wrong")
+}
+
+fn object_type_counts(log: &Ocel, otypes: &[String]) -> DataFrame {
+    let mut counts: HashMap<&str, u32> = otypes.iter().map(|ot| (ot.as_str(), 0)).collect();
+    let mut event_counts: HashMap<&str, usize> = HashMap::new();
+    log.objects.values().for_each(|obj| {
+        *counts.entry(obj.obj_type.as_str()).or_insert(0) += 1;
+        *event_counts.entry(obj.obj_type.as_str()).or_insert(0) += obj.events.len();
+    });
+
+    let mut rows: Vec<(&str, u32)> = counts.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    DataFrame::new(vec![Series::new("Object Type", rows.iter().map(|r| r.0).collect::<Vec<&str>>()),
+                        Series::new("Count", rows.iter().map(|r| r.1).collect::<Vec<u32>>()),
+                        Series::new("Mean Events per Object", rows.iter().map(|r| {
+                            if r.1 == 0 { return 0.0 }
+                            *event_counts.get(r.0).unwrap_or(&0) as f64 / r.1 as f64
+                        }).collect::<Vec<f64>>())]).expect("Data Table Creation went wrong")
+}
+
+fn attribute_completeness(log: &Ocel) -> DataFrame {
+    let mut names: Vec<&str> = vec![];
+    let mut scopes: Vec<&str> = vec![];
+    let mut present: Vec<u32> = vec![];
+    let mut ratio: Vec<f64> = vec![];
+
+    let declared: Vec<String> = match log.global_log.get("ocel:attribute-names") {
+        Some(Value::Array(attrs)) => attrs.iter().filter_map(|a| a.as_str().map(|s| s.to_string())).collect(),
+        _ => vec![]
+    };
+
+    // the declared names are shared by events and objects, an attribute belongs to the scope it
+    // occurs in and declared attributes that never occur are listed as unused
+    let mut event_attrs: HashMap<&str, u32> = HashMap::new();
+    log.events.values().for_each(|ev| ev.vmap.keys().for_each(|k| *event_attrs.entry(k.as_str()).or_insert(0) += 1));
+    let mut object_attrs: HashMap<&str, u32> = HashMap::new();
+    log.objects.values().for_each(|obj| obj.ovmap.keys().for_each(|k| *object_attrs.entry(k.as_str()).or_insert(0) += 1));
+    let unused_attrs: HashMap<&str, u32> = declared.iter()
+                                                   .filter(|a| !event_attrs.contains_key(a.as_str()) && !object_attrs.contains_key(a.as_str()))
+                                                   .map(|a| (a.as_str(), 0))
+                                                   .collect();
+
+    for (scope, attrs, total) in [("event", event_attrs, log.events.len()), ("object", object_attrs, log.objects.len()), ("unused", unused_attrs, 0)] {
+        let mut rows: Vec<(&str, u32)> = attrs.into_iter().collect();
+        rows.sort();
+        rows.iter().for_each(|(name, count)| {
+            names.push(*name);
+            scopes.push(scope);
+            present.push(*count);
+            ratio.push(if total == 0 { 0.0 } else { *count as f64 / total as f64 });
+        });
+    }
+
+    DataFrame::new(vec![Series::new("Attribute", names),
+                        Series::new("Scope", scopes),
+                        Series::new("Present", present),
+                        Series::new("Completeness", ratio)]).expect("Data Table Creation went wrong")
+}
+
+fn activity_object_type_cooccurrence(log: &Ocel, otypes: &[String]) -> DataFrame {
+    let ot_index: HashMap<&str, usize> = otypes.iter().enumerate().map(|(i, ot)| (ot.as_str(), i)).collect();
+    let act_index: HashMap<&str, usize> = log.activities.iter().enumerate().map(|(i, act)| (act.as_str(), i)).collect();
+    let mut matrix: Vec<Vec<u32>> = vec![vec![0; log.activities.len()]; otypes.len()];
+
+    log.events.values().for_each(|ev| {
+        if let Some(ai) = act_index.get(ev.activity.as_str()) {
+            let ev_otypes: HashSet<&str> = ev.omap.iter().filter_map(|oid| log.objects.get(oid)).map(|obj| obj.obj_type.as_str()).collect();
+            ev_otypes.iter().for_each(|ot| {
+                if let Some(oi) = ot_index.get(ot) {
+                    matrix[*oi][*ai] += 1;
+                }
+            });
+        }
+    });
+
+    let mut columns: Vec<Series> = vec![Series::new("Activity", log.activities.iter().map(|a| a.as_str()).collect::<Vec<&str>>())];
+    otypes.iter().zip(matrix).for_each(|(ot, counts)| columns.push(Series::new(ot.as_str(), counts)));
+    DataFrame::new(columns).expect("Data Table Creation went wrong")
+}
+
+fn time_summary(log: &Ocel) -> Vec<(String, Value)> {
+    let mut summary: Vec<(String, Value)> = vec![];
+    let start = log.events.values().map(|ev| ev.timestamp).min();
+    let end = log.events.values().map(|ev| ev.timestamp).max();
+
+    if let (Some(start), Some(end)) = (start, end) {
+        let duration = end - start;
+        let days = duration.num_seconds() as f64 / 86400.0;
+        summary.push(("Start".to_string(), json!(start.to_string())));
+        summary.push(("End".to_string(), json!(end.to_string())));
+        summary.push(("Duration (days)".to_string(), json!(days)));
+        if days > 0.0 {
+            summary.push(("Events per Day".to_string(), json!(log.events.len() as f64 / days)));
+        }
+    }
+
+    // object throughput time: time between the first and last event of an object
+    let lifetimes: Vec<f64> = log.objects.values().filter_map(|obj| {
+        let times = obj.events.iter().filter_map(|eid| log.events.get(eid)).map(|ev| ev.timestamp);
+        let first = times.clone().min()?;
+        let last = times.max()?;
+        Some((last - first).num_seconds() as f64)
+    }).collect();
+
+    if !lifetimes.is_empty() {
+        summary.push(("Mean Object Throughput (s)".to_string(), json!(lifetimes.iter().sum::<f64>() / lifetimes.len() as f64)));
+        summary.push(("Max Object Throughput (s)".to_string(), json!(lifetimes.iter().cloned().fold(0.0, f64::max))));
+    }
+
+    summary
+}
+
+pub fn describe_ocel(log: &Ocel) -> OcelDescription {
+    let otypes = get_object_types(log);
+
+    OcelDescription {
+        activities: activity_frequencies(log),
+        object_types: object_type_counts(log, &otypes),
+        events_per_object: distribution_table(log.objects.values().map(|obj| obj.events.len()), "Events per Object", "Object #"),
+        objects_per_event: distribution_table(log.events.values().map(|ev| ev.omap.len()), "Objects per Event", "Event #"),
+        attribute_completeness: attribute_completeness(log),
+        activity_object_types: activity_object_type_cooccurrence(log, &otypes),
+        summary: time_summary(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{event, object, log_declaring};
+
+    fn completeness_rows(df: &DataFrame) -> Vec<(String, String, u32, f64)> {
+        let names = df.column("Attribute").unwrap().utf8().unwrap().into_no_null_iter();
+        let scopes = df.column("Scope").unwrap().utf8().unwrap().into_no_null_iter();
+        let present = df.column("Present").unwrap().u32().unwrap().into_no_null_iter();
+        let ratio = df.column("Completeness").unwrap().f64().unwrap().into_no_null_iter();
+        names.zip(scopes).zip(present.zip(ratio)).map(|((n, s), (p, r))| (n.to_string(), s.to_string(), p, r)).collect()
+    }
+
+    #[test]
+    fn attributes_are_reported_in_the_scope_they_occur_in() {
+        let log = log_declaring(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1"], json!({"cost": 10})),
+                                     event("e2", "pay", "2022-01-02T10:00:00+00:00", &["o1", "o2"], json!({}))],
+                                vec![object("o1", "order", json!({"price": 5})),
+                                     object("o2", "item", json!({"price": 2, "weight": 1}))],
+                                &["discount"]);
+
+        let rows = completeness_rows(&describe_ocel(&log).attribute_completeness);
+        assert_eq!(rows, vec![("cost".to_string(), "event".to_string(), 1, 0.5),
+                              ("price".to_string(), "object".to_string(), 2, 1.0),
+                              ("weight".to_string(), "object".to_string(), 1, 0.5),
+                              ("discount".to_string(), "unused".to_string(), 0, 0.0)]);
+    }
+
+    #[test]
+    fn attribute_in_both_scopes_is_listed_twice() {
+        let log = log_declaring(vec![event("e1", "place", "2022-01-01T10:00:00+00:00", &["o1"], json!({"status": "new"}))],
+                                vec![object("o1", "order", json!({"status": "open"}))],
+                                &[]);
+
+        let rows = completeness_rows(&describe_ocel(&log).attribute_completeness);
+        assert_eq!(rows, vec![("status".to_string(), "event".to_string(), 1, 1.0),
+                              ("status".to_string(), "object".to_string(), 1, 1.0)]);
+    }
+}
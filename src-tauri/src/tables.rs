@@ -0,0 +1,225 @@
+use std::collections::{HashMap, HashSet};
+use polars::prelude::{DataFrame, DataType, Series, NamedFrom, AnyValue};
+use crate::provenance::ColumnProvenance;
+
+
+// a table entity as seen by the table utilities
+pub struct TableInput<'a> {
+    pub id: usize,
+    pub df: &'a DataFrame,
+    pub columns: &'a [ColumnProvenance]
+}
+
+pub struct SituationTable {
+    pub df: DataFrame,
+    pub columns: Vec<ColumnProvenance>,
+    pub dropped: usize,
+    pub unmatched: usize,
+    pub missing_ids: usize
+}
+
+// ids are kept as strings, whatever type the first column has
+pub fn id_column(df: &DataFrame) -> Result<Vec<Option<String>>, String> {
+    let ids = match df.get_columns().first() {
+        Some(ids) => ids,
+        None => {return Err("Table without columns".to_string());}
+    };
+    match ids.dtype() {
+        DataType::Utf8 => Ok(ids.utf8().unwrap().into_iter().map(|id| id.map(|i| i.to_string())).collect()),
+        _ => Ok((0..ids.len()).map(|i| Some(ids.get(i).to_string())).collect())
+    }
+}
+
+// reorder (and repeat) the rows of a series, missing rows become null
+pub fn align_series(series: &Series, rows: &[Option<usize>], name: &str) -> Series {
+    match series.dtype() {
+        DataType::Utf8 => {
+            let ca = series.utf8().unwrap();
+            Series::new(name, rows.iter().map(|r| r.and_then(|i| ca.get(i))).collect::<Vec<Option<&str>>>())
+        },
+        DataType::Boolean => {
+            let ca = series.bool().unwrap();
+            Series::new(name, rows.iter().map(|r| r.and_then(|i| ca.get(i))).collect::<Vec<Option<bool>>>())
+        },
+        _ => match series.cast(&DataType::Float64) {
+            Ok(numeric) => {
+                let ca = numeric.f64().unwrap();
+                Series::new(name, rows.iter().map(|r| r.and_then(|i| ca.get(i))).collect::<Vec<Option<f64>>>())
+            },
+            Err(_) => Series::new(name, rows.iter().map(|r| r.map(|i| series.get(i).to_string())).collect::<Vec<Option<String>>>())
+        }
+    }
+}
+
+// The situation target table decides the rows: every feature table is left joined on the id
+// column. Feature columns whose name is already taken get the rust id of their table appended.
+pub fn build_situation_table(target: &TableInput, features: &[TableInput], drop_missing: bool) -> Result<SituationTable, String> {
+    let target_ids = id_column(target.df)?;
+    let target_cols = target.df.get_columns();
+
+    // rows without an id or a target value (in any of the target columns) are dropped on request,
+    // otherwise rows without an id are kept with empty features
+    let mut rows: Vec<usize> = (0..target_ids.len()).collect();
+    let missing_ids = target_ids.iter().filter(|id| id.is_none()).count();
+    if drop_missing {
+        rows.retain(|i| target_ids[*i].is_some() && target_cols.iter().skip(1).all(|col| !matches!(col.get(*i), AnyValue::Null)));
+    }
+    let dropped = target_ids.len() - rows.len();
+
+    let mut series: Vec<Series> = vec![];
+    let mut columns: Vec<ColumnProvenance> = vec![];
+    let mut taken: HashSet<String> = HashSet::new();
+    let target_rows: Vec<Option<usize>> = rows.iter().map(|i| Some(*i)).collect();
+    for (i, col) in target_cols.iter().enumerate() {
+        let aligned = align_series(col, &target_rows, col.name());
+        taken.insert(col.name().to_string());
+        if let Some(prov) = target.columns.get(i) {
+            let mut prov = prov.clone();
+            prov.dtype = aligned.dtype().to_string();
+            columns.push(prov);
+        }
+        series.push(aligned);
+    }
+
+    let mut unmatched: HashSet<usize> = HashSet::new();
+    for table in features {
+        let index: HashMap<String, usize> = id_column(table.df)?.into_iter()
+                                                                 .enumerate()
+                                                                 .filter_map(|(i, id)| id.map(|id| (id, i)))
+                                                                 .collect();
+        let feature_rows: Vec<Option<usize>> = rows.iter().map(|i| target_ids[*i].as_ref().and_then(|id| index.get(id)).copied()).collect();
+        feature_rows.iter().enumerate().filter(|(_, r)| r.is_none()).for_each(|(i, _)| {unmatched.insert(i);});
+
+        for (i, col) in table.df.get_columns().iter().enumerate().skip(1) {
+            let mut name = col.name().to_string();
+            if taken.contains(&name) {
+                name = format!("{}:{}", name, table.id);
+            }
+            let aligned = align_series(col, &feature_rows, name.as_str());
+            taken.insert(name.to_string());
+            if let Some(prov) = table.columns.get(i) {
+                let mut prov = prov.clone();
+                prov.name = name;
+                prov.dtype = aligned.dtype().to_string();
+                columns.push(prov);
+            }
+            series.push(aligned);
+        }
+    }
+
+    let df = DataFrame::new(series).map_err(|e| e.to_string())?;
+    Ok(SituationTable { df, columns, dropped, unmatched: unmatched.len(), missing_ids })
+}
+
+// numeric view of a column, missing or non-numeric values become NaN. Text columns are not numeric.
+pub fn numeric_column(series: &Series) -> Option<Vec<f64>> {
+    if matches!(series.dtype(), DataType::Utf8) {
+        return None;
+    }
+    let numeric = series.cast(&DataType::Float64).ok()?;
+    Some(numeric.f64().unwrap().into_iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+}
+
+pub fn string_column(series: &Series) -> Vec<Option<String>> {
+    match series.dtype() {
+        DataType::Utf8 => series.utf8().unwrap().into_iter().map(|v| v.map(|s| s.to_string())).collect(),
+        _ => (0..series.len()).map(|i| match series.get(i) {
+            AnyValue::Null => None,
+            v => Some(v.to_string())
+        }).collect()
+    }
+}
+
+// The requested numeric columns (all numeric columns apart from the id column and `exclude` if
+// none are requested). Returns the column names and the values column by column.
+pub fn numeric_columns(df: &DataFrame, requested: &[&str], exclude: &[&str]) -> Result<(Vec<String>, Vec<Vec<f64>>), String> {
+    let mut names: Vec<String> = vec![];
+    let mut values: Vec<Vec<f64>> = vec![];
+    if requested.is_empty() {
+        for series in df.get_columns().iter().skip(1).filter(|s| !exclude.contains(&s.name())) {
+            if let Some(column) = numeric_column(series) {
+                names.push(series.name().to_string());
+                values.push(column);
+            }
+        }
+    } else {
+        for name in requested {
+            let series = df.column(name).map_err(|_| format!("Column {} does not exist", name))?;
+            match numeric_column(series) {
+                Some(column) => {
+                    names.push(name.to_string());
+                    values.push(column);
+                },
+                None => {return Err(format!("Column {} is not numeric", name));}
+            }
+        }
+    }
+
+    if names.is_empty() {
+        return Err("No numeric columns selected".to_string());
+    }
+    Ok((names, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::table_provenance;
+
+    fn target() -> DataFrame {
+        DataFrame::new(vec![
+            Series::new("Object", &[Some("o1"), None, Some("o3"), Some("o4")]),
+            Series::new("Target", &[Some(1.0), Some(2.0), Some(3.0), None])
+        ]).unwrap()
+    }
+
+    fn feature() -> DataFrame {
+        DataFrame::new(vec![
+            Series::new("Object", &["o3", "o1", "o9"]),
+            Series::new("Target", &[30.0, 10.0, 90.0]),
+            Series::new("Label", &["c", "a", "z"])
+        ]).unwrap()
+    }
+
+    #[test]
+    fn rows_without_id_are_kept_with_empty_features() {
+        let (target, feature) = (target(), feature());
+        let (target_columns, feature_columns) = (table_provenance(&target, "Target", &[1]), table_provenance(&feature, "Feature", &[2]));
+        let table = build_situation_table(&TableInput {id: 1, df: &target, columns: &target_columns},
+                                          &[TableInput {id: 2, df: &feature, columns: &feature_columns}], false).unwrap();
+
+        assert_eq!((table.df.height(), table.dropped, table.missing_ids), (4, 0, 1));
+        // the row without an id and o4 have no feature row
+        assert_eq!(table.unmatched, 2);
+        assert_eq!(table.df.get_column_names(), vec!["Object", "Target", "Target:2", "Label"]);
+        let joined: Vec<Option<f64>> = table.df.column("Target:2").unwrap().f64().unwrap().into_iter().collect();
+        assert_eq!(joined, vec![Some(10.0), None, Some(30.0), None]);
+        let labels: Vec<Option<&str>> = table.df.column("Label").unwrap().utf8().unwrap().into_iter().collect();
+        assert_eq!(labels, vec![Some("a"), None, Some("c"), None]);
+        assert_eq!(table.columns.iter().map(|c| c.name.as_str()).collect::<Vec<&str>>(), vec!["Object", "Target", "Target:2", "Label"]);
+    }
+
+    #[test]
+    fn rows_without_id_or_target_are_dropped_on_request() {
+        let (target, feature) = (target(), feature());
+        let (target_columns, feature_columns) = (table_provenance(&target, "Target", &[1]), table_provenance(&feature, "Feature", &[2]));
+        let table = build_situation_table(&TableInput {id: 1, df: &target, columns: &target_columns},
+                                          &[TableInput {id: 2, df: &feature, columns: &feature_columns}], true).unwrap();
+
+        assert_eq!((table.df.height(), table.dropped, table.missing_ids, table.unmatched), (2, 2, 1, 0));
+        let ids: Vec<Option<&str>> = table.df.column("Object").unwrap().utf8().unwrap().into_iter().collect();
+        assert_eq!(ids, vec![Some("o1"), Some("o3")]);
+    }
+
+    #[test]
+    fn numeric_ids_are_matched_as_strings() {
+        let target = DataFrame::new(vec![Series::new("Event", &[1i64, 2, 3]), Series::new("Target", &[1.0, 0.0, 1.0])]).unwrap();
+        let feature = DataFrame::new(vec![Series::new("Event", &[3i64, 1]), Series::new("Duration", &[5.0, 7.0])]).unwrap();
+        let table = build_situation_table(&TableInput {id: 1, df: &target, columns: &[]},
+                                          &[TableInput {id: 2, df: &feature, columns: &[]}], false).unwrap();
+
+        assert_eq!((table.missing_ids, table.unmatched), (0, 1));
+        let joined: Vec<Option<f64>> = table.df.column("Duration").unwrap().f64().unwrap().into_iter().collect();
+        assert_eq!(joined, vec![Some(7.0), None, Some(5.0)]);
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::str::FromStr;
+use chrono::{DateTime, Duration, Utc};
+use pmrs::objects::ocel::Ocel;
+use pmrs::algo::transformation::ocel::timeseries::{generate_time_series, TimeSeries, auto_timediff_binning};
+use serde_json::{Value, json};
+use crate::filtering::extract_sublog;
+
+
+// the names of the pmrs time series types offered in the plugin
+pub const SERIES_TYPES: [&str; 3] = ["ActivityCount", "ObjectCount", "UniqueObjectCount"];
+pub const BREAKDOWNS: [&str; 3] = ["None", "Activity", "ObjectType"];
+// upper bound on the number of bins of an explicit binning
+pub const MAX_BINS: usize = 10_000;
+
+pub fn parse_series_type(name: &str) -> Result<TimeSeries, String> {
+    TimeSeries::from_str(name).map_err(|_| format!("Unknown time series type {}", name))
+}
+
+// "90s", "15m", "2h", "1d" or "1w", the unit is required
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let amount: i64 = input[..split].parse().map_err(|_| format!("Could not parse duration {}", input))?;
+    let width = match input[split..].trim() {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        "" => {return Err(format!("The duration {} needs a unit (s, m, h, d or w)", input));},
+        unit => {return Err(format!("Unknown time unit {}", unit));}
+    };
+    if width <= Duration::zero() {
+        return Err("The duration has to be positive".to_string());
+    }
+    Ok(width)
+}
+
+pub struct Binning {
+    pub start: DateTime<Utc>,
+    pub width: Duration,
+    pub bins: usize
+}
+
+impl Binning {
+    // Bins between start and end (default: first and last event). The width is either given or
+    // derived from the number of bins. At most MAX_BINS bins are created.
+    pub fn new(log: &Ocel, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>, width: Option<Duration>, bins: Option<usize>) -> Result<Binning, String> {
+        let start = match start.or_else(|| log.events.values().map(|ev| ev.timestamp).min()) {
+            Some(start) => start,
+            None => {return Err("The log has no events".to_string());}
+        };
+        let end = end.or_else(|| log.events.values().map(|ev| ev.timestamp).max()).unwrap_or(start);
+        if end < start {
+            return Err("The end of the time series lies before its start".to_string());
+        }
+
+        let span = (end - start).num_milliseconds().max(1);
+        let width = match (width, bins) {
+            (Some(width), _) => width,
+            (None, Some(bins)) => Duration::milliseconds((span as f64 / bins.max(1) as f64).ceil() as i64).max(Duration::milliseconds(1)),
+            (None, None) => {return Err("An explicit binning needs a bin width or a bin count".to_string());}
+        };
+        // the last bin includes the end
+        let bins = span / width.num_milliseconds().max(1) + 1;
+        if bins > MAX_BINS as i64 {
+            return Err(format!("The binning would create {} bins, at most {} are allowed", bins, MAX_BINS));
+        }
+        Ok(Binning { start, width, bins: bins as usize })
+    }
+
+    fn bin(&self, timestamp: DateTime<Utc>) -> Option<usize> {
+        if timestamp < self.start {
+            return None;
+        }
+        let bin = ((timestamp - self.start).num_milliseconds() / self.width.num_milliseconds().max(1)) as usize;
+        if bin < self.bins { Some(bin) } else { None }
+    }
+
+    pub fn timestamps(&self) -> Vec<String> {
+        (0..self.bins).map(|b| (self.start + self.width * b as i32).format("%Y-%m-%d %H:%M:%S").to_string()).collect()
+    }
+}
+
+pub struct TimeSeriesColumn {
+    pub name: String,
+    pub series_type: String,
+    pub params: Option<Value>,
+    pub values: Vec<f64>
+}
+
+// the value of one series type for the events of a single bin, `objects` restricts the objects
+// that are counted. Counts the same as the pmrs time series.
+fn aggregate(log: &Ocel, series_type: &TimeSeries, events: &[usize], objects: &dyn Fn(&usize) -> bool) -> f64 {
+    match series_type {
+        TimeSeries::ObjectCount => events.iter().map(|eid| log.events[eid].omap.iter().filter(|oid| objects(oid)).count()).sum::<usize>() as f64,
+        TimeSeries::UniqueObjectCount => events.iter().flat_map(|eid| log.events[eid].omap.iter().filter(|oid| objects(oid))).collect::<HashSet<&usize>>().len() as f64,
+        _ => events.len() as f64
+    }
+}
+
+// Generate every requested series type, either for the whole log or broken down per activity or
+// per object type. Per object type, only events and objects of that type are counted.
+pub fn generate_series(log: &Ocel, binning: &Binning, series_types: &[&str], breakdown: &str) -> Result<Vec<TimeSeriesColumn>, String> {
+    let mut bins: Vec<Vec<usize>> = vec![vec![]; binning.bins];
+    log.events.iter().for_each(|(eid, ev)| {
+        if let Some(b) = binning.bin(ev.timestamp) {
+            bins[b].push(*eid);
+        }
+    });
+
+    let mut result: Vec<TimeSeriesColumn> = vec![];
+    for name in series_types {
+        let series_type = &parse_series_type(name)?;
+        match breakdown {
+            "Activity" => {
+                for act in &log.activities {
+                    let values = bins.iter().map(|events| {
+                        let matching: Vec<usize> = events.iter().copied().filter(|eid| &log.events[eid].activity == act).collect();
+                        aggregate(log, series_type, &matching, &|_| true)
+                    }).collect();
+                    result.push(TimeSeriesColumn { name: format!("{}:{}", name, act), series_type: name.to_string(), params: Some(json!({"activity": act})), values });
+                }
+            },
+            "ObjectType" => {
+                let object_types: BTreeMap<&usize, &str> = log.objects.iter().map(|(oid, obj)| (oid, obj.obj_type.as_str())).collect();
+                let types: BTreeSet<&str> = object_types.values().copied().collect();
+                for ot in types {
+                    let of_type = |oid: &usize| object_types.get(oid).map_or(false, |t| *t == ot);
+                    let values = bins.iter().map(|events| {
+                        let matching: Vec<usize> = events.iter().copied().filter(|eid| log.events[eid].omap.iter().any(|oid| of_type(oid))).collect();
+                        aggregate(log, series_type, &matching, &of_type)
+                    }).collect();
+                    result.push(TimeSeriesColumn { name: format!("{}:{}", name, ot), series_type: name.to_string(), params: Some(json!({"object_type": ot})), values });
+                }
+            },
+            _ => {
+                let values = bins.iter().map(|events| aggregate(log, series_type, events, &|_| true)).collect();
+                result.push(TimeSeriesColumn { name: name.to_string(), series_type: name.to_string(), params: None, values });
+            }
+        }
+    }
+    Ok(result)
+}
+
+// the parts of a log a breakdown consists of: (name suffix, parameters, sub-log), none without
+// a breakdown
+fn breakdown_logs(log: &Ocel, breakdown: &str) -> Vec<(String, Value, Ocel)> {
+    match breakdown {
+        "Activity" => log.activities.iter().map(|act| {
+            let events: HashSet<usize> = log.events.iter().filter(|(_, ev)| &ev.activity == act).map(|(eid, _)| *eid).collect();
+            let objects: HashSet<usize> = events.iter().flat_map(|eid| log.events[eid].omap.iter().copied()).collect();
+            (act.to_string(), json!({"activity": act}), extract_sublog(log, &events, &objects))
+        }).collect(),
+        "ObjectType" => {
+            let types: BTreeSet<&str> = log.objects.values().map(|obj| obj.obj_type.as_str()).collect();
+            types.into_iter().map(|ot| {
+                let objects: HashSet<usize> = log.objects.iter().filter(|(_, obj)| obj.obj_type == ot).map(|(oid, _)| *oid).collect();
+                let events: HashSet<usize> = log.events.iter().filter(|(_, ev)| ev.omap.iter().any(|oid| objects.contains(oid))).map(|(eid, _)| *eid).collect();
+                (ot.to_string(), json!({"object_type": ot}), extract_sublog(log, &events, &objects))
+            }).collect()
+        },
+        _ => vec![]
+    }
+}
+
+// Generate every requested series type with the pmrs time series and the automatic binning of
+// `log`. A breakdown generates the series of the sub-log of every activity / object type on the
+// same bins.
+pub fn generate_auto_series(log: &Ocel, series_types: &[&str], breakdown: &str) -> Result<Vec<TimeSeriesColumn>, String> {
+    let parts = breakdown_logs(log, breakdown);
+    let mut result: Vec<TimeSeriesColumn> = vec![];
+    for name in series_types {
+        if parts.is_empty() {
+            let values: Vec<f64> = generate_time_series::<f64>(log, auto_timediff_binning(log), parse_series_type(name)?);
+            result.push(TimeSeriesColumn { name: name.to_string(), series_type: name.to_string(), params: None, values });
+            continue;
+        }
+        for (suffix, params, part) in &parts {
+            let values: Vec<f64> = generate_time_series::<f64>(part, auto_timediff_binning(log), parse_series_type(name)?);
+            if result.first().map_or(false, |first| first.values.len() != values.len()) {
+                return Err(format!("The time series of {} does not cover the bins of the log", suffix));
+            }
+            result.push(TimeSeriesColumn { name: format!("{}:{}", name, suffix), series_type: name.to_string(), params: Some(params.clone()), values });
+        }
+    }
+    Ok(result)
+}
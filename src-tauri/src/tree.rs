@@ -0,0 +1,325 @@
+use std::cmp::Ordering;
+use polars::prelude::{DataFrame, DataType};
+use serde::{Serialize, Deserialize};
+use strum::EnumString;
+use crate::dfg::escape;
+use crate::tables::{numeric_columns, numeric_column, string_column};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumString)]
+pub enum Task {
+    Classification,
+    Regression
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "node")]
+pub enum TreeNode {
+    // value is the predicted class index for classification trees
+    Leaf { value: f64, samples: usize, impurity: f64 },
+    Split { feature: usize, threshold: f64, samples: usize, impurity: f64, left: Box<TreeNode>, right: Box<TreeNode> }
+}
+
+impl TreeNode {
+    fn samples(&self) -> usize {
+        match self {
+            TreeNode::Leaf { samples, .. } | TreeNode::Split { samples, .. } => *samples
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TreeConfig {
+    pub max_depth: usize,
+    pub min_samples_leaf: usize
+}
+
+// column-major features and the encoded target of a table, classification targets are class
+// indices into `classes`. Missing values are NaN.
+pub struct TrainingData {
+    pub task: Task,
+    pub target: String,
+    pub features: Vec<String>,
+    pub classes: Vec<String>,
+    pub x: Vec<Vec<f64>>,
+    pub y: Vec<f64>
+}
+
+// Text and boolean targets are learned by classification, numbers by regression unless a task is given.
+pub fn training_data(df: &DataFrame, target: &str, features: &[&str], task: Option<Task>) -> Result<TrainingData, String> {
+    let target_series = df.column(target).map_err(|_| format!("Target column {} does not exist", target))?;
+    let task = task.unwrap_or(match target_series.dtype() {
+        DataType::Utf8 | DataType::Boolean => Task::Classification,
+        _ => Task::Regression
+    });
+    let (features, x) = numeric_columns(df, features, &[target])?;
+    if features.iter().any(|f| f == target) {
+        return Err("The target column cannot be a feature".to_string());
+    }
+
+    let mut classes: Vec<String> = vec![];
+    let y: Vec<f64> = match task {
+        Task::Classification => {
+            let labels = string_column(target_series);
+            classes = labels.iter().flatten().cloned().collect();
+            classes.sort();
+            classes.dedup();
+            labels.iter().map(|l| match l {
+                Some(l) => classes.binary_search(l).map(|c| c as f64).unwrap_or(f64::NAN),
+                None => f64::NAN
+            }).collect()
+        },
+        Task::Regression => match numeric_column(target_series) {
+            Some(y) => y,
+            None => {return Err(format!("Target column {} is not numeric", target));}
+        }
+    };
+
+    Ok(TrainingData { task, target: target.to_string(), features, classes, x, y })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionTree {
+    pub task: Task,
+    pub target: String,
+    pub features: Vec<String>,
+    // class labels of classification trees, leaves store the index into this list
+    pub classes: Vec<String>,
+    pub importances: Vec<f64>,
+    pub root: TreeNode
+}
+
+fn impurity(task: Task, y: &[f64], rows: &[usize], n_classes: usize) -> f64 {
+    if rows.is_empty() {
+        return 0.0;
+    }
+    let n = rows.len() as f64;
+    match task {
+        Task::Classification => {
+            let mut counts = vec![0.0; n_classes];
+            rows.iter().for_each(|r| counts[y[*r] as usize] += 1.0);
+            1.0 - counts.iter().map(|c| (c / n) * (c / n)).sum::<f64>()
+        },
+        Task::Regression => {
+            let mean = rows.iter().map(|r| y[*r]).sum::<f64>() / n;
+            rows.iter().map(|r| (y[*r] - mean).powi(2)).sum::<f64>() / n
+        }
+    }
+}
+
+fn leaf_value(task: Task, y: &[f64], rows: &[usize], n_classes: usize) -> f64 {
+    match task {
+        Task::Classification => {
+            let mut counts = vec![0usize; n_classes];
+            rows.iter().for_each(|r| counts[y[*r] as usize] += 1);
+            // ties go to the first class
+            (0..n_classes).fold(0, |best, c| if counts[c] > counts[best] { c } else { best }) as f64
+        },
+        Task::Regression => rows.iter().map(|r| y[*r]).sum::<f64>() / rows.len().max(1) as f64
+    }
+}
+
+// Running statistics of one side of a split, used to sweep over the sorted values of a feature
+// without recomputing the impurity from scratch.
+struct SideStats {
+    n: f64,
+    sum: f64,
+    sum_sq: f64,
+    counts: Vec<f64>
+}
+
+impl SideStats {
+    fn new(n_classes: usize) -> SideStats {
+        SideStats { n: 0.0, sum: 0.0, sum_sq: 0.0, counts: vec![0.0; n_classes] }
+    }
+
+    fn add(&mut self, task: Task, value: f64, sign: f64) {
+        self.n += sign;
+        match task {
+            Task::Classification => self.counts[value as usize] += sign,
+            Task::Regression => {
+                self.sum += sign * value;
+                self.sum_sq += sign * value * value;
+            }
+        }
+    }
+
+    // impurity weighted by the number of samples
+    fn weighted_impurity(&self, task: Task) -> f64 {
+        if self.n <= 0.0 {
+            return 0.0;
+        }
+        match task {
+            Task::Classification => self.n - self.counts.iter().map(|c| c * c).sum::<f64>() / self.n,
+            Task::Regression => (self.sum_sq - self.sum * self.sum / self.n).max(0.0)
+        }
+    }
+}
+
+// best (feature, threshold, weighted child impurity) over all features
+fn best_split(task: Task, x: &[Vec<f64>], y: &[f64], rows: &[usize], n_classes: usize, min_leaf: usize) -> Option<(usize, f64, f64)> {
+    let mut best: Option<(usize, f64, f64)> = None;
+    for (f, column) in x.iter().enumerate() {
+        let mut sorted: Vec<usize> = rows.to_vec();
+        sorted.sort_by(|a, b| column[*a].partial_cmp(&column[*b]).unwrap_or(Ordering::Equal));
+
+        let mut left = SideStats::new(n_classes);
+        let mut right = SideStats::new(n_classes);
+        sorted.iter().for_each(|r| right.add(task, y[*r], 1.0));
+
+        for i in 0..sorted.len() - 1 {
+            left.add(task, y[sorted[i]], 1.0);
+            right.add(task, y[sorted[i]], -1.0);
+            let (curr, next) = (column[sorted[i]], column[sorted[i + 1]]);
+            if curr == next || i + 1 < min_leaf || sorted.len() - i - 1 < min_leaf {
+                continue;
+            }
+            let score = left.weighted_impurity(task) + right.weighted_impurity(task);
+            if best.map_or(true, |(_, _, s)| score < s - 1e-12) {
+                best = Some((f, (curr + next) / 2.0, score));
+            }
+        }
+    }
+    best
+}
+
+fn grow(data: &TrainingData, rows: Vec<usize>, config: &TreeConfig, depth: usize, importances: &mut [f64]) -> TreeNode {
+    let (task, x, y, n_classes) = (data.task, &data.x, &data.y, data.classes.len());
+    let node_impurity = impurity(task, y, &rows, n_classes);
+    let leaf = TreeNode::Leaf { value: leaf_value(task, y, &rows, n_classes), samples: rows.len(), impurity: node_impurity };
+    if depth >= config.max_depth || rows.len() < 2 * config.min_samples_leaf.max(1) || node_impurity <= 1e-12 {
+        return leaf;
+    }
+
+    match best_split(task, x, y, &rows, n_classes, config.min_samples_leaf.max(1)) {
+        Some((feature, threshold, score)) if score < node_impurity * rows.len() as f64 - 1e-12 => {
+            importances[feature] += node_impurity * rows.len() as f64 - score;
+            let (left, right): (Vec<usize>, Vec<usize>) = rows.iter().partition(|r| x[feature][**r] <= threshold);
+            TreeNode::Split {
+                feature,
+                threshold,
+                samples: rows.len(),
+                impurity: node_impurity,
+                left: Box::new(grow(data, left, config, depth + 1, importances)),
+                right: Box::new(grow(data, right, config, depth + 1, importances))
+            }
+        },
+        _ => leaf
+    }
+}
+
+// CART on the given rows. Rows with a missing feature or target value are not used for training.
+pub fn fit_tree(data: &TrainingData, rows: &[usize], config: &TreeConfig) -> DecisionTree {
+    let rows: Vec<usize> = rows.iter().copied().filter(|r| !data.y[*r].is_nan() && data.x.iter().all(|col| !col[*r].is_nan())).collect();
+    let mut importances = vec![0.0; data.features.len()];
+    let root = grow(data, rows, config, 0, &mut importances);
+
+    let total: f64 = importances.iter().sum();
+    if total > 0.0 {
+        importances.iter_mut().for_each(|imp| *imp /= total);
+    }
+    DecisionTree { task: data.task, target: data.target.to_string(), features: data.features.clone(), classes: data.classes.clone(), importances, root }
+}
+
+impl DecisionTree {
+    // missing feature values follow the child that saw more training samples
+    pub fn predict(&self, row: &[f64]) -> f64 {
+        let mut node = &self.root;
+        loop {
+            match node {
+                TreeNode::Leaf { value, .. } => {return *value;},
+                TreeNode::Split { feature, threshold, left, right, .. } => {
+                    let value = row[*feature];
+                    node = if value.is_nan() {
+                        if left.samples() >= right.samples() { left } else { right }
+                    } else if value <= *threshold {
+                        left
+                    } else {
+                        right
+                    };
+                }
+            }
+        }
+    }
+
+    pub fn label(&self, prediction: f64) -> String {
+        match self.task {
+            Task::Classification => self.classes.get(prediction as usize).cloned().unwrap_or_default(),
+            Task::Regression => prediction.to_string()
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        fn depth(node: &TreeNode) -> usize {
+            match node {
+                TreeNode::Leaf { .. } => 0,
+                TreeNode::Split { left, right, .. } => 1 + depth(left).max(depth(right))
+            }
+        }
+        depth(&self.root)
+    }
+
+    pub fn leaves(&self) -> usize {
+        fn leaves(node: &TreeNode) -> usize {
+            match node {
+                TreeNode::Leaf { .. } => 1,
+                TreeNode::Split { left, right, .. } => leaves(left) + leaves(right)
+            }
+        }
+        leaves(&self.root)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph DecisionTree {\n  node [shape=box, style=rounded];\n");
+        let mut counter = 0;
+        self.node_to_dot(&self.root, &mut counter, &mut dot);
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn node_to_dot(&self, node: &TreeNode, counter: &mut usize, dot: &mut String) -> usize {
+        let id = *counter;
+        *counter += 1;
+        match node {
+            TreeNode::Leaf { value, samples, impurity } => {
+                dot.push_str(&format!("  n{} [label=\"{} = {}\\nsamples = {}\\nimpurity = {:.4}\", style=\"rounded,filled\", fillcolor=\"#eeeeee\"];\n",
+                                      id, escape(&self.target), escape(&self.label(*value)), samples, impurity));
+            },
+            TreeNode::Split { feature, threshold, samples, impurity, left, right } => {
+                dot.push_str(&format!("  n{} [label=\"{} <= {:.4}\\nsamples = {}\\nimpurity = {:.4}\"];\n",
+                                      id, escape(&self.features[*feature]), threshold, samples, impurity));
+                let left_id = self.node_to_dot(left, counter, dot);
+                let right_id = self.node_to_dot(right, counter, dot);
+                dot.push_str(&format!("  n{} -> n{} [label=\"true\"];\n", id, left_id));
+                dot.push_str(&format!("  n{} -> n{} [label=\"false\"];\n", id, right_id));
+            }
+        }
+        id
+    }
+}
+
+// accuracy for classification, r2 / mae / rmse for regression
+pub fn evaluate(tree: &DecisionTree, data: &TrainingData, rows: &[usize]) -> Vec<(String, f64)> {
+    let (x, y) = (&data.x, &data.y);
+    let rows: Vec<usize> = rows.iter().copied().filter(|r| !y[*r].is_nan()).collect();
+    if rows.is_empty() {
+        return vec![];
+    }
+    let predictions: Vec<f64> = rows.iter().map(|r| tree.predict(&x.iter().map(|col| col[*r]).collect::<Vec<f64>>())).collect();
+    let n = rows.len() as f64;
+    match tree.task {
+        Task::Classification => {
+            let correct = rows.iter().zip(&predictions).filter(|(r, p)| y[**r] == **p).count();
+            vec![("accuracy".to_string(), correct as f64 / n)]
+        },
+        Task::Regression => {
+            let mean = rows.iter().map(|r| y[*r]).sum::<f64>() / n;
+            let ss_res: f64 = rows.iter().zip(&predictions).map(|(r, p)| (y[*r] - p).powi(2)).sum();
+            let ss_tot: f64 = rows.iter().map(|r| (y[*r] - mean).powi(2)).sum();
+            let mae: f64 = rows.iter().zip(&predictions).map(|(r, p)| (y[*r] - p).abs()).sum::<f64>() / n;
+            vec![("r2".to_string(), if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else { 0.0 }),
+                 ("mae".to_string(), mae),
+                 ("rmse".to_string(), (ss_res / n).sqrt())]
+        }
+    }
+}
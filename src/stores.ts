@@ -33,6 +33,24 @@ export const TemplateEntityStore = writable({
             name: "New Table Entity",
             type: "table"
         }
+    },
+    dfg: {
+        metadata: {
+            name: "Object-Centric Directly-Follows Graph",
+            type: "dfg"
+        }
+    },
+    petrinet: {
+        metadata: {
+            name: "Object-Centric Petri Net",
+            type: "petrinet"
+        }
+    },
+    model: {
+        metadata: {
+            name: "Trained Model",
+            type: "model"
+        }
     }
 });
 